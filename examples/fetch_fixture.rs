@@ -0,0 +1,46 @@
+//! Saves the `__NEXT_DATA__` JSON of a Goodreads book page to `tests/fixtures/<id>.json`.
+//!
+//! ```sh
+//! cargo run --example fetch_fixture -- 4556058
+//! ```
+
+use grscraper::{GoodreadsClient, GoodreadsId};
+use std::{env, fs, path::Path, process};
+
+#[tokio::main]
+async fn main() {
+    let Some(id) = env::args().nth(1) else {
+        eprintln!("Usage: fetch_fixture <goodreads id>");
+        process::exit(2);
+    };
+
+    let id = match GoodreadsId::parse(&id) {
+        Ok(id) => id,
+        Err(error) => {
+            eprintln!("{error:?}");
+            process::exit(2);
+        }
+    };
+
+    let data = match GoodreadsClient::default()
+        .fetch_page_data(id.as_str())
+        .await
+    {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("Failed to fetch book {id}: {error:?}");
+            process::exit(1);
+        }
+    };
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{id}.json"));
+    let json = serde_json::to_string_pretty(&data).expect("Page data must serialize");
+
+    if let Err(error) = fs::write(&path, json + "\n") {
+        eprintln!("Failed to write {}: {error}", path.display());
+        process::exit(1);
+    }
+    println!("Saved {}", path.display());
+}
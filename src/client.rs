@@ -0,0 +1,544 @@
+use crate::{
+    errors::ScraperError,
+    goodreads_id_fetcher::{
+        BookEdition, SearchResult, fetch_editions, fetch_ids_from_isbn, fetch_shelf, search_books,
+        search_stream, verify_isbn_exists,
+    },
+    http::{HttpFetcher, HttpResponse},
+    metadata_fetcher::{
+        BookMetadata, fetch_cover, fetch_page_data, fetch_quotes, fetch_series, fetch_shelves,
+        refresh_metadata,
+    },
+    request_builder::{EmptyState, MetadataRequestBuilder},
+};
+use futures::{Stream, StreamExt, stream};
+use log::trace;
+use reqwest::{
+    Client, Proxy,
+    header::{COOKIE, HeaderMap, HeaderName, HeaderValue, USER_AGENT},
+};
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+type RequestCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A reusable Goodreads session.
+///
+/// The client owns a configured HTTP client, so connections and TLS sessions are reused across
+/// lookups instead of being renegotiated for every request. Cloning is cheap, and clones share the
+/// same connection pool and request count. Requests go through `reqwest` unless a custom `HttpFetcher` is set.
+#[derive(Clone)]
+pub struct GoodreadsClient {
+    fetcher: Arc<dyn HttpFetcher>,
+    on_request: Option<RequestCallback>,
+    request_delay: Option<Duration>,
+    request_count: Arc<AtomicU64>,
+}
+
+impl Default for GoodreadsClient {
+    fn default() -> Self {
+        GoodreadsClient::from_client(Client::default())
+    }
+}
+
+impl fmt::Debug for GoodreadsClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GoodreadsClient")
+            .field("on_request", &self.on_request.is_some())
+            .field("request_delay", &self.request_delay)
+            .field("request_count", &self.request_count())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Builder for configuring a `GoodreadsClient`.
+#[derive(Default)]
+#[must_use]
+pub struct GoodreadsClientBuilder {
+    headers: HeaderMap,
+    invalid_header: Option<HeaderName>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    on_request: Option<RequestCallback>,
+    request_delay: Option<Duration>,
+    fetcher: Option<Arc<dyn HttpFetcher>>,
+}
+
+impl fmt::Debug for GoodreadsClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GoodreadsClientBuilder")
+            .field("headers", &self.headers)
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("proxies", &self.proxies)
+            .field("on_request", &self.on_request.is_some())
+            .field("request_delay", &self.request_delay)
+            .field("fetcher", &self.fetcher.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GoodreadsClientBuilder {
+    /// Sets the `User-Agent` header sent with every request.
+    pub fn with_user_agent(self, user_agent: &str) -> Self {
+        self.with_header(USER_AGENT, user_agent, false)
+    }
+
+    /// Merges `headers` into the headers sent with every request, replacing previously set values of
+    /// the same headers. `with_user_agent` and `with_cookies` write to the same headers, so the last call wins.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Sets a total timeout for every request, from connecting until the body is read.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for establishing connections, to fail fast when Goodreads is unreachable.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a timeout for each read of a response, which restarts whenever data arrives.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes requests through an HTTP(S) or SOCKS5 proxy. Can be called several times to add proxies
+    /// for different schemes, e.g. `Proxy::https("socks5://127.0.0.1:1080")`.
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Sends `cookies` as the `Cookie` header of every request, e.g. `"session-id=...; at-main=..."` copied
+    /// from a logged-in browser session. This exposes content that Goodreads hides from anonymous visitors.
+    pub fn with_cookies(self, cookies: &str) -> Self {
+        self.with_header(COOKIE, cookies, true)
+    }
+
+    /// Calls `callback` with the URL of every request before it is sent, e.g. the search, ISBN lookup
+    /// and book page URLs. Useful to diagnose mismatched lookups without enabling global logging.
+    pub fn with_on_request(mut self, callback: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_request = Some(Arc::new(callback));
+        self
+    }
+
+    /// Waits `delay` between the successive requests of batch and stream APIs: the lookups of `metadata_stream`,
+    /// the pages of `search_stream`, `fetch_shelf` and author book lists, and the books of `with_author_only`.
+    /// Single lookups are not delayed. Defaults to no delay.
+    pub fn with_request_delay(mut self, delay: Duration) -> Self {
+        self.request_delay = Some(delay);
+        self
+    }
+
+    /// Sends every request through `fetcher` instead of `reqwest`, e.g. a custom transport or canned pages
+    /// in tests. Headers, timeouts and proxies only configure the `reqwest` client, so they are ignored then.
+    pub fn with_fetcher(mut self, fetcher: impl HttpFetcher + 'static) -> Self {
+        self.fetcher = Some(Arc::new(fetcher));
+        self
+    }
+
+    pub fn build(self) -> Result<GoodreadsClient, ScraperError> {
+        if let Some(name) = self.invalid_header {
+            return Err(ScraperError::ParseError(format!(
+                "Invalid value for header {name}"
+            )));
+        }
+
+        if let Some(fetcher) = self.fetcher {
+            return Ok(GoodreadsClient {
+                fetcher,
+                on_request: self.on_request,
+                request_delay: self.request_delay,
+                request_count: Arc::default(),
+            });
+        }
+
+        let mut builder = Client::builder().default_headers(self.headers);
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+
+        for proxy in self.proxies {
+            builder = builder.proxy(proxy);
+        }
+
+        Ok(GoodreadsClient {
+            fetcher: Arc::new(builder.build()?),
+            on_request: self.on_request,
+            request_delay: self.request_delay,
+            request_count: Arc::default(),
+        })
+    }
+
+    // Invalid values are reported by `build`, keeping the option methods chainable.
+    fn with_header(mut self, name: HeaderName, value: &str, sensitive: bool) -> Self {
+        match HeaderValue::from_str(value) {
+            Ok(mut value) => {
+                value.set_sensitive(sensitive);
+                self.headers.insert(name, value);
+            }
+            Err(_) => self.invalid_header = Some(name),
+        }
+        self
+    }
+}
+
+impl GoodreadsClient {
+    pub fn builder() -> GoodreadsClientBuilder {
+        GoodreadsClientBuilder::default()
+    }
+
+    /// Creates a session around an existing, already configured `reqwest::Client`.
+    pub fn from_client(http: Client) -> Self {
+        GoodreadsClient::from_fetcher(http)
+    }
+
+    /// Creates a session that sends its requests through `fetcher`. See `HttpFetcher`.
+    pub fn from_fetcher(fetcher: impl HttpFetcher + 'static) -> Self {
+        GoodreadsClient {
+            fetcher: Arc::new(fetcher),
+            on_request: None,
+            request_delay: None,
+            request_count: Arc::default(),
+        }
+    }
+
+    /// Starts a metadata request that is executed through this session.
+    pub fn request(&self) -> MetadataRequestBuilder<EmptyState> {
+        MetadataRequestBuilder::from_client(self.clone())
+    }
+
+    pub async fn metadata_by_id(&self, id: &str) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request().with_id(id).execute().await
+    }
+
+    pub async fn metadata_by_isbn(&self, isbn: &str) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request().with_isbn(isbn).execute().await
+    }
+
+    /// Fetches the metadata of the default edition of a work, given the work's Goodreads ID.
+    pub async fn metadata_by_work_id(
+        &self,
+        work_id: &str,
+    ) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request().with_work_id(work_id).execute().await
+    }
+
+    pub async fn metadata_by_asin(&self, asin: &str) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request().with_asin(asin).execute().await
+    }
+
+    pub async fn metadata_by_title(
+        &self,
+        title: &str,
+    ) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request().with_title(title).execute().await
+    }
+
+    pub async fn metadata_by_title_and_author(
+        &self,
+        title: &str,
+        author: &str,
+    ) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request()
+            .with_title(title)
+            .with_author(author)
+            .execute()
+            .await
+    }
+
+    /// Looks `title` up in the book list of the author with the Goodreads ID `author_id`.
+    pub async fn metadata_by_title_and_author_id(
+        &self,
+        title: &str,
+        author_id: &str,
+    ) -> Result<Option<BookMetadata>, ScraperError> {
+        self.request()
+            .with_title(title)
+            .with_author_id(author_id)
+            .execute()
+            .await
+    }
+
+    /// Checks whether Goodreads resolves `isbn` to a book, without scraping the book's metadata.
+    pub async fn verify_isbn_exists(&self, isbn: &str) -> Result<bool, ScraperError> {
+        verify_isbn_exists(self, isbn).await
+    }
+
+    /// Fetches the raw `__NEXT_DATA__` JSON embedded in a book page, which all metadata is parsed from.
+    /// Useful to capture test fixtures or to inspect data the scraper does not extract.
+    pub async fn fetch_page_data(
+        &self,
+        goodreads_id: &str,
+    ) -> Result<serde_json::Value, ScraperError> {
+        fetch_page_data(self, goodreads_id).await
+    }
+
+    /// Lists every Goodreads ID an ISBN resolves to. Most ISBNs resolve to a single book, but some are
+    /// shared by several Goodreads entries, in which case ISBN lookups pick the first one listed.
+    pub async fn fetch_isbn_candidates(&self, isbn: &str) -> Result<Vec<String>, ScraperError> {
+        fetch_ids_from_isbn(self, isbn).await
+    }
+
+    /// Fetches the popular quotes of a work, given the work's Goodreads ID (see `BookMetadata::work_id`).
+    /// This is a separate request from the metadata scrape. Works without quotes yield an empty list.
+    pub async fn fetch_quotes(&self, work_id: &str) -> Result<Vec<String>, ScraperError> {
+        fetch_quotes(self, work_id).await
+    }
+
+    /// Fetches the most popular user shelves of a work with their counts, given the work's Goodreads ID.
+    /// Unlike genres, shelves are free-form tags applied by readers.
+    pub async fn fetch_shelves(&self, work_id: &str) -> Result<Vec<(String, i64)>, ScraperError> {
+        fetch_shelves(self, work_id).await
+    }
+
+    /// Downloads the cover image of `metadata` at full resolution, following redirects.
+    /// Returns `ScraperError::ScrapeError` if the book has no `image_url`.
+    pub async fn fetch_cover(&self, metadata: &BookMetadata) -> Result<Vec<u8>, ScraperError> {
+        fetch_cover(self, metadata).await
+    }
+
+    /// Re-fetches `metadata` by its `goodreads_id`, e.g. for periodic syncs of a local catalog.
+    /// Returns `ScraperError::ScrapeError` if the book has no `goodreads_id`.
+    pub async fn refresh(&self, metadata: &BookMetadata) -> Result<BookMetadata, ScraperError> {
+        refresh_metadata(self, metadata).await
+    }
+
+    /// Lists the Goodreads IDs of every book on a user's shelf (e.g. "read" or "to-read"), following all of
+    /// the shelf's pages. Returns `ScraperError::Private` if the user's profile is private.
+    pub async fn fetch_shelf(
+        &self,
+        user_id: &str,
+        shelf_name: &str,
+    ) -> Result<Vec<String>, ScraperError> {
+        fetch_shelf(self, user_id, shelf_name).await
+    }
+
+    /// Lists the numbered entries of a series in reading order as `(position, goodreads_id)` pairs, given the
+    /// series' Goodreads ID (see `BookSeries::id`). Omnibus entries spanning several positions are skipped.
+    pub async fn fetch_series(&self, series_id: &str) -> Result<Vec<(f32, String)>, ScraperError> {
+        fetch_series(self, series_id).await
+    }
+
+    /// Lists up to 100 editions of a work, given the work's Goodreads ID.
+    pub async fn fetch_editions(&self, work_id: &str) -> Result<Vec<BookEdition>, ScraperError> {
+        fetch_editions(self, work_id).await
+    }
+
+    /// Fetches the metadata of every ISBN in `isbns`, running up to `concurrency` lookups at once.
+    /// Results are yielded as `(isbn, result)` pairs in completion order, and ISBNs are only pulled
+    /// from the iterator as slots free up, so arbitrarily large inputs use bounded memory.
+    pub fn metadata_stream(
+        &self,
+        isbns: impl IntoIterator<Item = String>,
+        concurrency: usize,
+    ) -> impl Stream<Item = (String, Result<Option<BookMetadata>, ScraperError>)> {
+        let client = self.clone();
+        let pacing = self.clone();
+        stream::iter(isbns)
+            .enumerate()
+            .then(move |(index, isbn)| {
+                let pacing = pacing.clone();
+                async move {
+                    if index > 0 {
+                        pacing.pace().await;
+                    }
+                    isbn
+                }
+            })
+            .map(move |isbn| {
+                let client = client.clone();
+                async move {
+                    let result = client.metadata_by_isbn(&isbn).await;
+                    (isbn, result)
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+    }
+
+    /// Like `metadata_stream`, but skips the ISBNs in `completed`, such as the ones a previous interrupted run
+    /// already stored, so a large batch can be restarted without fetching them again. ISBNs are compared as given.
+    pub fn resume_metadata_stream(
+        &self,
+        isbns: impl IntoIterator<Item = String>,
+        completed: HashSet<String>,
+        concurrency: usize,
+    ) -> impl Stream<Item = (String, Result<Option<BookMetadata>, ScraperError>)> {
+        let pending = isbns
+            .into_iter()
+            .filter(move |isbn| !completed.contains(isbn));
+        self.metadata_stream(pending, concurrency)
+    }
+
+    /// Runs an arbitrary Goodreads search and returns the first page of results as listed, without
+    /// any title or author matching.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>, ScraperError> {
+        search_books(self, query).await
+    }
+
+    /// Streams search results for `query`, following the result pages until one is empty or
+    /// `max_pages` pages have been fetched. Pages are only requested as the stream is polled.
+    pub fn search_stream(
+        &self,
+        query: &str,
+        max_pages: usize,
+    ) -> impl Stream<Item = Result<SearchResult, ScraperError>> {
+        search_stream(self.clone(), query, max_pages)
+    }
+
+    /// How many HTTP requests this session and its clones have sent so far, including failed ones, e.g. to track
+    /// a request budget. The difference around an `execute` call is what the lookup cost, usually one to three
+    /// requests, as long as no other lookup shares the session meanwhile. See also `with_on_request`.
+    pub fn request_count(&self) -> u64 {
+        self.request_count.load(Ordering::Relaxed)
+    }
+
+    // Called between the successive requests of batch and stream APIs.
+    pub(crate) async fn pace(&self) {
+        if let Some(delay) = self.request_delay {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    pub(crate) async fn get(&self, url: &str) -> Result<HttpResponse, ScraperError> {
+        trace!("GET {url}");
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        if let Some(on_request) = &self.on_request {
+            on_request(url);
+        }
+
+        #[cfg(feature = "tracing")]
+        let start = std::time::Instant::now();
+        let response = self.fetcher.get(url).await?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            url,
+            body_len = response.body.len(),
+            elapsed_ms = start.elapsed().as_millis(),
+            "Goodreads request finished"
+        );
+        if response.url != url {
+            trace!("{url} resolved to {}", response.url);
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_with_proxy_test() {
+        let client = GoodreadsClient::builder()
+            .with_proxy(Proxy::all("socks5://127.0.0.1:1080").unwrap())
+            .with_proxy(Proxy::https("http://127.0.0.1:8080").unwrap())
+            .build();
+
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn build_with_timeouts_test() {
+        let client = GoodreadsClient::builder()
+            .with_timeout(Duration::from_secs(90))
+            .with_connect_timeout(Duration::from_secs(5))
+            .with_read_timeout(Duration::from_secs(30))
+            .build();
+
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn build_with_cookies_test() {
+        let client = GoodreadsClient::builder()
+            .with_cookies("session-id=123-456; locale=en")
+            .build();
+        assert!(client.is_ok());
+
+        let client = GoodreadsClient::builder()
+            .with_cookies("session-id=123\n456")
+            .build();
+        assert!(matches!(client, Err(ScraperError::ParseError(_))));
+    }
+
+    #[test]
+    fn with_headers_merges_test() {
+        let mut headers = HeaderMap::new();
+        headers.insert("accept-language", HeaderValue::from_static("en-US"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("custom-agent"));
+
+        let builder = GoodreadsClient::builder()
+            .with_user_agent("first-agent")
+            .with_cookies("session-id=1")
+            .with_headers(headers)
+            .with_user_agent("last-agent");
+
+        assert_eq!(builder.headers.len(), 3);
+        assert_eq!(builder.headers[USER_AGENT], "last-agent");
+        assert!(builder.headers[COOKIE].is_sensitive());
+        assert!(builder.build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn request_delay_test() {
+        let client = GoodreadsClient::builder()
+            .with_request_delay(Duration::from_millis(50))
+            .build()
+            .unwrap();
+
+        let start = std::time::Instant::now();
+        client.pace().await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let client = GoodreadsClient::default();
+        let start = std::time::Instant::now();
+        client.pace().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn on_request_callback_test() {
+        let urls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&urls);
+        let client = GoodreadsClient::builder()
+            .with_on_request(move |url| recorded.lock().unwrap().push(url.to_string()))
+            .build()
+            .unwrap();
+
+        // The request itself may fail offline, the callback runs before it is sent.
+        let _ = client.get("http://127.0.0.1:9/book/show/1").await;
+        assert_eq!(
+            *urls.lock().unwrap(),
+            vec!["http://127.0.0.1:9/book/show/1"]
+        );
+        assert_eq!(client.clone().request_count(), 1);
+    }
+}
@@ -1,24 +1,76 @@
 use scraper::error::SelectorErrorKind;
+use std::{convert::Infallible, time::Duration};
 
 /// Custom error type for handling errors in the Goodreads metadata scraper.
 #[derive(Debug)]
 pub enum ScraperError {
     /// Error that occurs during the HTTP request to Goodreads, originating from `reqwest`.
+    #[cfg(feature = "net")]
     FetchError(reqwest::Error),
     /// Error encountered while parsing the HTML document, originating from `scraper`.
     ParseError(String),
     /// Non-recoverable error encountered while scraping the HTML document. Indicates expected content was missing.
     ScrapeError(String),
+    /// A field expected in the page data was missing. `path` is the dotted JSON path of the field
+    /// (e.g. `props.pageProps.apolloState.Book:kca://book/....title`), to tell schema changes apart
+    /// from books that simply lack the field.
+    MissingField { path: String, goodreads_id: String },
     /// Error encountered during JSON serialization, originating from `serde_json`.
     SerializeError(serde_json::Error),
+    /// The requested book could not be found on Goodreads.
+    NotFound,
+    /// The book page exists but no longer holds a book, e.g. because the book was deleted or merged away.
+    /// Common with old IDs from exports. Unlike `MissingField`, this does not indicate a parsing problem.
+    Unavailable,
+    /// Goodreads responded with an unsuccessful HTTP status other than 429, such as 404 (gone), 403 (forbidden)
+    /// or 503 (temporarily unavailable). Contains the status code.
+    HttpStatus(u16),
+    /// Goodreads responded with HTTP 429. Contains the delay requested via the `Retry-After` header, if any.
+    RateLimited { retry_after: Option<Duration> },
+    /// The requested content is private, such as the shelf of a user with a private profile.
+    Private,
+    /// Goodreads served an anti-bot challenge or CAPTCHA page instead of the requested content.
+    Blocked,
 }
 
+impl ScraperError {
+    // Copies the error, except for the wrapped `reqwest` and `serde_json` errors, which cannot be copied.
+    #[cfg(feature = "net")]
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        let error = match self {
+            ScraperError::FetchError(_) | ScraperError::SerializeError(_) => return None,
+            ScraperError::ParseError(message) => ScraperError::ParseError(message.clone()),
+            ScraperError::ScrapeError(message) => ScraperError::ScrapeError(message.clone()),
+            ScraperError::MissingField { path, goodreads_id } => ScraperError::MissingField {
+                path: path.clone(),
+                goodreads_id: goodreads_id.clone(),
+            },
+            ScraperError::NotFound => ScraperError::NotFound,
+            ScraperError::Unavailable => ScraperError::Unavailable,
+            ScraperError::HttpStatus(status) => ScraperError::HttpStatus(*status),
+            ScraperError::RateLimited { retry_after } => ScraperError::RateLimited {
+                retry_after: *retry_after,
+            },
+            ScraperError::Private => ScraperError::Private,
+            ScraperError::Blocked => ScraperError::Blocked,
+        };
+        Some(error)
+    }
+}
+
+#[cfg(feature = "net")]
 impl From<reqwest::Error> for ScraperError {
     fn from(error: reqwest::Error) -> Self {
         ScraperError::FetchError(error)
     }
 }
 
+impl From<Infallible> for ScraperError {
+    fn from(error: Infallible) -> Self {
+        match error {}
+    }
+}
+
 impl From<SelectorErrorKind<'static>> for ScraperError {
     fn from(error: SelectorErrorKind<'static>) -> Self {
         ScraperError::ParseError(error.to_string())
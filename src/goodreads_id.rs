@@ -0,0 +1,92 @@
+use crate::errors::ScraperError;
+use std::fmt;
+
+/// A Goodreads book ID, the numeric identifier in book URLs such as `https://www.goodreads.com/book/show/4556058`.
+///
+/// `GoodreadsId::parse` trims surrounding whitespace and rejects anything that is not made of digits only,
+/// so malformed IDs fail without a request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GoodreadsId(String);
+
+impl GoodreadsId {
+    pub fn parse(id: &str) -> Result<Self, ScraperError> {
+        let trimmed = id.trim();
+        if trimmed.is_empty() || !trimmed.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ScraperError::ParseError(format!(
+                "Invalid Goodreads ID {id}"
+            )));
+        }
+
+        Ok(GoodreadsId(trimmed.to_string()))
+    }
+
+    /// The ID as it appears in book URLs.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for GoodreadsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for GoodreadsId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for GoodreadsId {
+    type Error = ScraperError;
+
+    fn try_from(id: &str) -> Result<Self, Self::Error> {
+        GoodreadsId::parse(id)
+    }
+}
+
+impl TryFrom<&String> for GoodreadsId {
+    type Error = ScraperError;
+
+    fn try_from(id: &String) -> Result<Self, Self::Error> {
+        GoodreadsId::parse(id)
+    }
+}
+
+impl TryFrom<String> for GoodreadsId {
+    type Error = ScraperError;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        GoodreadsId::parse(&id)
+    }
+}
+
+impl From<u64> for GoodreadsId {
+    fn from(id: u64) -> Self {
+        GoodreadsId(id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_goodreads_id_test() {
+        assert_eq!(GoodreadsId::parse("4556058").unwrap().as_str(), "4556058");
+        assert_eq!(
+            GoodreadsId::parse(" 175254\n").unwrap().to_string(),
+            "175254"
+        );
+        assert_eq!(GoodreadsId::from(30_312_855).as_str(), "30312855");
+
+        assert!(matches!(
+            GoodreadsId::parse("bad_id"),
+            Err(ScraperError::ParseError(_))
+        ));
+        assert!(GoodreadsId::parse("").is_err());
+        assert!(GoodreadsId::parse("4556058-the-lightning-thief").is_err());
+        assert!(GoodreadsId::try_from("-1").is_err());
+    }
+}
@@ -1,86 +1,621 @@
-use crate::errors::ScraperError;
-use reqwest::get;
+use crate::{
+    client::GoodreadsClient, errors::ScraperError, goodreads_id::GoodreadsId,
+    http::check_challenge, isbn::Isbn,
+};
+use derive_new::new;
+use futures::stream::{self, Stream, StreamExt};
+use log::{error, warn};
+use regex::Regex;
 use scraper::{Html, Selector};
 use serde_json::Value;
+use std::{
+    fmt,
+    sync::{Arc, LazyLock},
+};
 use urlencoding::encode;
 
-pub async fn verify_id_exists(id: &str) -> bool {
+const STRONG_TITLE_MATCH: f32 = 0.9;
+
+static PUBLISHED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"published\s+(-?\d+)").expect("Regex must be valid"));
+static EDITION_PUBLISHED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*published\s+(?:[a-z]+\s+)?(?:\d{1,2}(?:st|nd|rd|th)\s+)?(-?\d{1,4})\b")
+        .expect("Regex must be valid")
+});
+static RATING_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+(?:\.\d+)?)\s+avg rating\D+?([\d,]+)\s+ratings?").expect("Regex must be valid")
+});
+
+/// A single row of the Goodreads search results table.
+#[derive(Debug, Clone, new, PartialEq)]
+pub struct SearchResult {
+    /// The title of the book as displayed in the search results.
+    pub title: String,
+    /// The book's authors, joined with ", ".
+    pub authors: String,
+    /// The Goodreads ID of the book.
+    pub goodreads_id: String,
+    /// The publication year shown in the search results, if any.
+    pub publication_year: Option<i32>,
+    /// The average rating shown in the search results, if any.
+    pub average_rating: Option<f32>,
+    /// The number of ratings shown in the search results, if any.
+    pub ratings_count: Option<i64>,
+}
+
+/// The outcome of a title search, explaining why no book was selected when nothing matched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchOutcome {
+    /// A search result matched the query.
+    Found(SearchResult),
+    /// Goodreads returned no results for the query.
+    NoResults,
+    /// Goodreads returned results, but none of them matched the query.
+    NoMatch { candidates: Vec<SearchResult> },
+}
+
+impl SearchOutcome {
+    /// The Goodreads ID of the matched book, if any.
+    pub fn goodreads_id(&self) -> Option<&str> {
+        match self {
+            SearchOutcome::Found(result) => Some(&result.goodreads_id),
+            _ => None,
+        }
+    }
+
+    fn unmatched(candidates: Vec<SearchResult>) -> Self {
+        if candidates.is_empty() {
+            SearchOutcome::NoResults
+        } else {
+            SearchOutcome::NoMatch { candidates }
+        }
+    }
+}
+
+/// A single edition of a work, as listed on the Goodreads editions page.
+#[derive(Debug, Clone, new, PartialEq)]
+pub struct BookEdition {
+    /// The Goodreads ID of the edition.
+    pub goodreads_id: String,
+    /// The title of the edition.
+    pub title: String,
+    /// The format of the edition, such as "Paperback" or "Kindle Edition".
+    pub format: Option<String>,
+    /// The ISBN of the edition, if listed.
+    pub isbn: Option<String>,
+    /// The language of the edition, if listed.
+    pub language: Option<String>,
+    /// The year the edition was published, if listed.
+    #[new(default)]
+    pub publication_year: Option<i32>,
+}
+
+/// Decides whether a search result matches, given the found title and authors (joined with ", "),
+/// then the searched title and author.
+pub type Matcher = Arc<dyn Fn(&str, &str, &str, Option<&str>) -> bool + Send + Sync>;
+
+/// Options that refine how title-based searches pick a candidate, and how many editions `execute_all` returns.
+#[derive(Clone, Default)]
+pub struct SearchOptions {
+    pub year: Option<i32>,
+    pub min_rating: Option<f32>,
+    pub min_ratings_count: Option<i64>,
+    pub exact_title: bool,
+    pub all_editions: bool,
+    pub max_editions: Option<usize>,
+    pub max_candidates: Option<usize>,
+    pub matcher: Option<Matcher>,
+    pub series: Option<String>,
+}
+
+impl fmt::Debug for SearchOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchOptions")
+            .field("year", &self.year)
+            .field("min_rating", &self.min_rating)
+            .field("min_ratings_count", &self.min_ratings_count)
+            .field("exact_title", &self.exact_title)
+            .field("all_editions", &self.all_editions)
+            .field("max_editions", &self.max_editions)
+            .field("max_candidates", &self.max_candidates)
+            .field("matcher", &self.matcher.is_some())
+            .field("series", &self.series)
+            .finish()
+    }
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client), err(Debug))
+)]
+pub async fn verify_id_exists(
+    client: &GoodreadsClient,
+    id: &GoodreadsId,
+) -> Result<bool, ScraperError> {
     let url = format!("https://www.goodreads.com/book/show/{id}");
-    let response = get(&url).await.expect("Failed to fetch book page");
-    response.status().is_success()
+    match client.get(&url).await {
+        Ok(_) => Ok(true),
+        Err(ScraperError::HttpStatus(404 | 410)) => Ok(false),
+        Err(error) => Err(error),
+    }
 }
 
-pub async fn fetch_id_from_isbn(isbn: &str) -> Result<Option<String>, ScraperError> {
-    let url = format!("https://www.goodreads.com/search?q={}", encode(isbn));
-    let document = Html::parse_document(&get(&url).await?.text().await?);
+pub async fn verify_isbn_exists(
+    client: &GoodreadsClient,
+    isbn: &str,
+) -> Result<bool, ScraperError> {
+    Ok(fetch_id_from_isbn(client, isbn).await?.is_some())
+}
 
-    let metadata_selector = Selector::parse(r#"script[id="__NEXT_DATA__"]"#)?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client), err(Debug))
+)]
+pub async fn fetch_id_from_isbn(
+    client: &GoodreadsClient,
+    isbn: &str,
+) -> Result<Option<String>, ScraperError> {
+    Ok(fetch_ids_from_isbn(client, isbn).await?.into_iter().next())
+}
 
-    let metadata = match document.select(&metadata_selector).next() {
-        Some(metadata) => &metadata.text().collect::<String>(),
-        None => return Ok(None),
-    };
+/// Resolves an ISBN to every matching Goodreads ID. Goodreads usually redirects an ISBN search straight to
+/// the book, giving a single ID, but ISBNs shared by several entries lead to a list of candidates instead,
+/// which are returned in the listed order.
+pub async fn fetch_ids_from_isbn(
+    client: &GoodreadsClient,
+    isbn: &str,
+) -> Result<Vec<String>, ScraperError> {
+    let html = fetch_isbn_search(client, isbn).await?;
+    parse_isbn_search(&html)
+}
+
+/// Resolves an ISBN like `fetch_id_from_isbn`, also returning the book's page data when Goodreads redirected
+/// the search to the book page, so callers needing more than the ID don't have to fetch that page again.
+pub(crate) async fn fetch_isbn_match(
+    client: &GoodreadsClient,
+    isbn: &str,
+) -> Result<Option<(String, Option<Value>)>, ScraperError> {
+    let html = fetch_isbn_search(client, isbn).await?;
+    if let Some((goodreads_id, page_data)) = parse_redirect_page(&html)? {
+        return Ok(Some((goodreads_id, Some(page_data))));
+    }
+
+    let goodreads_id = parse_isbn_search(&html)?.into_iter().next();
+    Ok(goodreads_id.map(|goodreads_id| (goodreads_id, None)))
+}
+
+async fn fetch_isbn_search(client: &GoodreadsClient, isbn: &str) -> Result<String, ScraperError> {
+    // Valid ISBNs are searched without separators. Anything else is searched as given.
+    let isbn = Isbn::parse(isbn).map_or_else(|_| isbn.to_string(), |isbn| isbn.to_string());
+    let url = format!("https://www.goodreads.com/search?q={}", encode(&isbn));
+    Ok(client.get(&url).await?.text())
+}
+
+fn parse_isbn_search(html: &str) -> Result<Vec<String>, ScraperError> {
+    if let Some(goodreads_id) = parse_search_redirect(html)? {
+        return Ok(vec![goodreads_id]);
+    }
+
+    // Not redirected to a book: either no match or a disambiguation list of candidates.
+    let mut goodreads_ids: Vec<String> = Vec::new();
+    for result in parse_search_results(html)? {
+        if !goodreads_ids.contains(&result.goodreads_id) {
+            goodreads_ids.push(result.goodreads_id);
+        }
+    }
+
+    Ok(goodreads_ids)
+}
+
+// The ID of the book a search redirected to, if it did.
+fn parse_search_redirect(html: &str) -> Result<Option<String>, ScraperError> {
+    Ok(parse_redirect_page(html)?.map(|(goodreads_id, _)| goodreads_id))
+}
 
-    let metadata: Value = serde_json::from_str(metadata)?;
+// The ID and page data of the book a search redirected to, if it did.
+fn parse_redirect_page(html: &str) -> Result<Option<(String, Value)>, ScraperError> {
+    let document = Html::parse_document(html);
+    let metadata_selector = Selector::parse(r#"script[id="__NEXT_DATA__"]"#)?;
+    let Some(metadata) = document.select(&metadata_selector).next() else {
+        return Ok(None);
+    };
 
+    let metadata: Value = serde_json::from_str(&metadata.text().collect::<String>())?;
     let goodreads_id = metadata["props"]["pageProps"]["params"]["book_id"]
         .as_str()
-        .expect("Failed to extract Goodreads ID from ISBN search results");
+        .map(|id| id.chars().take_while(char::is_ascii_digit).collect());
 
-    let goodreads_id = goodreads_id
-        .chars()
-        .take_while(|c| c.is_numeric())
-        .collect::<String>();
+    Ok(goodreads_id.map(|goodreads_id| (goodreads_id, metadata)))
+}
 
-    Ok(Some(goodreads_id))
+/// Resolves an Amazon ASIN (e.g. a Kindle edition) to a Goodreads ID. Goodreads redirects most ASIN searches
+/// straight to the book; otherwise a search returning exactly one book is accepted.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client), err(Debug))
+)]
+pub async fn fetch_id_from_asin(
+    client: &GoodreadsClient,
+    asin: &str,
+) -> Result<Option<String>, ScraperError> {
+    let asin = asin.trim().to_uppercase();
+    let url = format!("https://www.goodreads.com/search?q={}", encode(&asin));
+    let html = client.get(&url).await?.text();
+    parse_asin_search(&html)
 }
 
-pub async fn fetch_id_from_title(title: &str) -> Result<Option<String>, ScraperError> {
-    let results = search_books(title).await?;
+// Unlike ISBNs, ASINs are only trusted when the search redirects or lists a single book.
+fn parse_asin_search(html: &str) -> Result<Option<String>, ScraperError> {
+    if let Some(goodreads_id) = parse_search_redirect(html)? {
+        return Ok(Some(goodreads_id));
+    }
 
-    for (found_title, _, found_id) in results {
-        if matches(&found_title, title) {
-            return Ok(Some(found_id));
-        }
+    match parse_search_results(html)?.as_slice() {
+        [result] => Ok(Some(result.goodreads_id.clone())),
+        _ => Ok(None),
+    }
+}
+
+/// Resolves a work ID to the Goodreads ID of the work's default (most popular) edition.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client), err(Debug))
+)]
+pub async fn fetch_id_from_work_id(
+    client: &GoodreadsClient,
+    work_id: &str,
+) -> Result<Option<String>, ScraperError> {
+    let url = format!(
+        "https://www.goodreads.com/work/best_book/{}",
+        encode(work_id)
+    );
+    let response = match client.get(&url).await {
+        Ok(response) => response,
+        Err(ScraperError::HttpStatus(404 | 410)) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    let goodreads_id = response.book_id();
+    if goodreads_id.is_none() {
+        check_challenge(&response.text())?;
     }
 
-    Ok(None)
+    Ok(goodreads_id)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client, options), err(Debug))
+)]
+pub async fn fetch_id_from_title(
+    client: &GoodreadsClient,
+    title: &str,
+    options: &SearchOptions,
+) -> Result<Option<String>, ScraperError> {
+    let outcome = search_title(client, title, options).await?;
+    Ok(outcome.goodreads_id().map(str::to_string))
 }
 
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client, options), err(Debug))
+)]
 pub async fn fetch_id_from_title_and_author(
+    client: &GoodreadsClient,
     title: &str,
     author: &str,
+    options: &SearchOptions,
 ) -> Result<Option<String>, ScraperError> {
-    let results = search_books(title).await?;
+    let outcome = search_title_and_author(client, title, author, options).await?;
+    Ok(outcome.goodreads_id().map(str::to_string))
+}
+
+pub async fn search_title(
+    client: &GoodreadsClient,
+    title: &str,
+    options: &SearchOptions,
+) -> Result<SearchOutcome, ScraperError> {
+    let results = search_books(client, title).await?;
+    if let Some(result) = select_candidate(&results, title, None, options) {
+        return Ok(SearchOutcome::Found(result.clone()));
+    }
+
+    Ok(SearchOutcome::unmatched(results))
+}
+
+pub async fn search_title_and_author(
+    client: &GoodreadsClient,
+    title: &str,
+    author: &str,
+    options: &SearchOptions,
+) -> Result<SearchOutcome, ScraperError> {
+    let mut results = search_books(client, title).await?;
+    if let Some(result) = select_candidate(&results, title, Some(author), options) {
+        return Ok(SearchOutcome::Found(result.clone()));
+    }
+
+    let more_results = search_books(client, &format!("{title} {author}")).await?;
+    if let Some(result) = select_candidate(&more_results, title, Some(author), options) {
+        return Ok(SearchOutcome::Found(result.clone()));
+    }
 
-    for (found_title, found_author, found_id) in results {
-        if matches(&found_title, title) && matches(&found_author, author) {
-            return Ok(Some(found_id));
+    for result in more_results {
+        if !results
+            .iter()
+            .any(|r| r.goodreads_id == result.goodreads_id)
+        {
+            results.push(result);
         }
     }
 
-    let results = search_books(&format!("{title} {author}")).await?;
+    if let Some(result) = title_only_candidate(&results, title, options) {
+        warn!(
+            "No result matched author \"{author}\", falling back to the only strong title match by \"{}\"",
+            result.authors
+        );
+        return Ok(SearchOutcome::Found(result.clone()));
+    }
+
+    Ok(SearchOutcome::unmatched(results))
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client, options), err(Debug))
+)]
+pub async fn fetch_id_from_author_books(
+    client: &GoodreadsClient,
+    author_id: &str,
+    title: &str,
+    options: &SearchOptions,
+) -> Result<Option<String>, ScraperError> {
+    let outcome = search_author_books(client, author_id, title, options).await?;
+    Ok(outcome.goodreads_id().map(str::to_string))
+}
+
+/// Looks for `title` in the book list of the author with the Goodreads ID `author_id`, following the
+/// list's pages until a book matches. Avoids the false positives of a site-wide search.
+pub async fn search_author_books(
+    client: &GoodreadsClient,
+    author_id: &str,
+    title: &str,
+    options: &SearchOptions,
+) -> Result<SearchOutcome, ScraperError> {
+    let mut results: Vec<SearchResult> = Vec::new();
+
+    for page in 1.. {
+        if page > 1 {
+            client.pace().await;
+        }
+        let url = format!(
+            "https://www.goodreads.com/author/list/{}?per_page=100&page={page}",
+            encode(author_id)
+        );
+        let html = client.get(&url).await?.text();
+        let page_results = parse_search_results(&html)?;
+
+        if let Some(result) = select_candidate(&page_results, title, None, options) {
+            return Ok(SearchOutcome::Found(result.clone()));
+        }
 
-    for (found_title, found_author, found_id) in results {
-        if matches(&found_title, title) && matches(&found_author, author) {
-            return Ok(Some(found_id));
+        let known = results.len();
+        for result in page_results {
+            if !results
+                .iter()
+                .any(|r| r.goodreads_id == result.goodreads_id)
+            {
+                results.push(result);
+            }
         }
+
+        // Pages past the end may be empty or repeat the last page.
+        if results.len() == known
+            || options
+                .max_candidates
+                .is_some_and(|max| results.len() >= max)
+        {
+            break;
+        }
+    }
+
+    if let Some(result) = title_only_candidate(&results, title, options) {
+        return Ok(SearchOutcome::Found(result.clone()));
     }
 
-    Ok(None)
+    Ok(SearchOutcome::unmatched(results))
+}
+
+/// Searches for books by `author`, keeping only the results that list a matching author.
+pub async fn search_author(
+    client: &GoodreadsClient,
+    author: &str,
+) -> Result<Vec<SearchResult>, ScraperError> {
+    let results = search_books(client, author).await?;
+    Ok(results
+        .into_iter()
+        .filter(|result| matches(&result.authors, author))
+        .collect())
 }
 
-async fn search_books(query: &str) -> Result<Vec<(String, String, String)>, ScraperError> {
-    let url = format!("https://www.goodreads.com/search?q={}", encode(query));
+fn select_candidate<'a>(
+    results: &'a [SearchResult],
+    title: &str,
+    author: Option<&str>,
+    options: &SearchOptions,
+) -> Option<&'a SearchResult> {
+    let candidates: Vec<_> = limit_candidates(results, options)
+        .filter(|result| {
+            let matched = match &options.matcher {
+                Some(matcher) => matcher(&result.title, &result.authors, title, author),
+                None => {
+                    title_matches(&result.title, title, options)
+                        && author.is_none_or(|a| matches(&result.authors, a))
+                }
+            };
+            matched && meets_rating_thresholds(result, options)
+        })
+        .collect();
 
-    let document = Html::parse_document(&get(&url).await?.text().await?);
+    // Candidates in the requested series win over the others, but a search is not failed just because
+    // none of them lists it.
+    let in_series: Vec<_> = match &options.series {
+        Some(series) => candidates
+            .iter()
+            .copied()
+            .filter(|result| series_name(&result.title).is_some_and(|name| matches(name, series)))
+            .collect(),
+        None => Vec::new(),
+    };
+    let candidates = if in_series.is_empty() {
+        candidates
+    } else {
+        in_series
+    };
+
+    let first = *candidates.first()?;
+    let Some(year) = options.year else {
+        return Some(first);
+    };
+
+    let preferred = candidates
+        .into_iter()
+        .find(|result| result.publication_year == Some(year));
+
+    Some(preferred.unwrap_or(first))
+}
+
+// Author names are often formatted differently on Goodreads. When nothing matched the author, a single
+// candidate whose title (almost) exactly matches is still accepted. Series suffixes such as
+// "(Graceling Realm, #2)" are ignored for this comparison. A custom matcher has the final say, so there is
+// no fallback with one.
+fn title_only_candidate<'a>(
+    results: &'a [SearchResult],
+    title: &str,
+    options: &SearchOptions,
+) -> Option<&'a SearchResult> {
+    if options.matcher.is_some() {
+        return None;
+    }
+
+    let mut candidates = limit_candidates(results, options).filter(|result| {
+        let found = strip_series_suffix(&result.title);
+        let strong = if options.exact_title {
+            normalize(found) == normalize(title)
+        } else {
+            similarity(found, title) >= STRONG_TITLE_MATCH
+        };
+        strong && meets_rating_thresholds(result, options)
+    });
+
+    match (candidates.next(), candidates.next()) {
+        (Some(result), None) => Some(result),
+        _ => None,
+    }
+}
+
+// Only the first `max_candidates` results of a search are considered, in the order Goodreads ranks them.
+fn limit_candidates<'a>(
+    results: &'a [SearchResult],
+    options: &SearchOptions,
+) -> impl Iterator<Item = &'a SearchResult> {
+    results
+        .iter()
+        .take(options.max_candidates.unwrap_or(usize::MAX))
+}
+
+// Exact matching compares whole titles, ignoring a series suffix such as "(Graceling Realm, #2)".
+fn title_matches(found: &str, title: &str, options: &SearchOptions) -> bool {
+    if options.exact_title {
+        normalize(strip_series_suffix(found)) == normalize(title)
+    } else {
+        matches(found, title)
+    }
+}
+
+fn strip_series_suffix(title: &str) -> &str {
+    match title.rsplit_once(" (") {
+        Some((title, series)) if series.ends_with(')') => title,
+        _ => title,
+    }
+}
+
+// The series name of a search result title such as "The Last Olympian (Percy Jackson and the Olympians, #5)".
+fn series_name(title: &str) -> Option<&str> {
+    let (_, suffix) = title.rsplit_once(" (")?;
+    let series = suffix.strip_suffix(')')?;
+    Some(series.rsplit_once(", #").map_or(series, |(name, _)| name))
+}
+
+// Candidates without rating stats are rejected whenever a threshold is set.
+fn meets_rating_thresholds(result: &SearchResult, options: &SearchOptions) -> bool {
+    let rating_ok = options
+        .min_rating
+        .is_none_or(|min| result.average_rating.is_some_and(|rating| rating >= min));
+    let count_ok = options
+        .min_ratings_count
+        .is_none_or(|min| result.ratings_count.is_some_and(|count| count >= min));
+
+    rating_ok && count_ok
+}
+
+pub fn search_stream(
+    client: GoodreadsClient,
+    query: &str,
+    max_pages: usize,
+) -> impl Stream<Item = Result<SearchResult, ScraperError>> {
+    let query = query.to_string();
+
+    stream::unfold(Some(1), move |page| {
+        let client = client.clone();
+        let query = query.clone();
+        async move {
+            let page = page.filter(|p| *p <= max_pages)?;
+            if page > 1 {
+                client.pace().await;
+            }
+            match search_page(&client, &query, page).await {
+                Ok(results) if results.is_empty() => None,
+                Ok(results) => Some((results.into_iter().map(Ok).collect(), Some(page + 1))),
+                Err(e) => Some((vec![Err(e)], None)),
+            }
+        }
+    })
+    .flat_map(stream::iter)
+}
+
+pub async fn search_books(
+    client: &GoodreadsClient,
+    query: &str,
+) -> Result<Vec<SearchResult>, ScraperError> {
+    search_page(client, query, 1).await
+}
+
+async fn search_page(
+    client: &GoodreadsClient,
+    query: &str,
+    page: usize,
+) -> Result<Vec<SearchResult>, ScraperError> {
+    let url = format!(
+        "https://www.goodreads.com/search?q={}&page={page}",
+        encode(query)
+    );
+
+    let html = client.get(&url).await?.text();
+    parse_search_results(&html)
+}
+
+// Search results and author book lists share the same table layout.
+fn parse_search_results(html: &str) -> Result<Vec<SearchResult>, ScraperError> {
+    let document = Html::parse_document(html);
     let book_selector = Selector::parse(r#"tr[itemtype="http://schema.org/Book"]"#)?;
     let title_selector = Selector::parse(r#"a[class="bookTitle"]"#)?;
     let author_selector = Selector::parse(r#"a[class="authorName"]"#)?;
 
     let mut results = Vec::new();
+    let mut rows = 0;
 
     for book in document.select(&book_selector) {
+        rows += 1;
         let Some(title) = book.select(&title_selector).next() else {
             continue;
         };
@@ -99,23 +634,226 @@ async fn search_books(query: &str) -> Result<Vec<(String, String, String)>, Scra
             .collect::<Vec<_>>()
             .join(", ");
 
-        results.push((found_title, found_authors, found_id));
+        let row_text = book.text().collect::<String>();
+        let found_year = PUBLISHED_RE
+            .captures(&row_text)
+            .and_then(|captures| captures[1].parse().ok());
+        let (found_rating, found_ratings_count) = parse_rating_stats(&row_text);
+
+        results.push(SearchResult::new(
+            found_title,
+            found_authors,
+            found_id,
+            found_year,
+            found_rating,
+            found_ratings_count,
+        ));
+    }
+
+    if results.is_empty() {
+        check_challenge(html)?;
+    }
+
+    if results.is_empty() && rows > 0 {
+        error!("Failed to parse search results");
+        return Err(ScraperError::ScrapeError(
+            "Failed to parse search results".to_string(),
+        ));
     }
 
     Ok(results)
 }
 
+fn parse_rating_stats(text: &str) -> (Option<f32>, Option<i64>) {
+    let Some(captures) = RATING_RE.captures(text) else {
+        return (None, None);
+    };
+
+    let rating = captures[1].parse().ok();
+    let count = captures[2].replace(',', "").parse().ok();
+    (rating, count)
+}
+
+/// Lists the Goodreads IDs of every book on a public user shelf, following the shelf's pages.
+pub async fn fetch_shelf(
+    client: &GoodreadsClient,
+    user_id: &str,
+    shelf_name: &str,
+) -> Result<Vec<String>, ScraperError> {
+    let mut ids: Vec<String> = Vec::new();
+
+    for page in 1.. {
+        if page > 1 {
+            client.pace().await;
+        }
+        let url = format!(
+            "https://www.goodreads.com/review/list/{}?shelf={}&per_page=100&page={page}",
+            encode(user_id),
+            encode(shelf_name)
+        );
+        let response = client.get(&url).await?;
+        if response.path().starts_with("/user/sign_in") {
+            return Err(ScraperError::Private);
+        }
+
+        let html = response.text();
+        let page_ids = parse_shelf_page(&Html::parse_document(&html))?;
+        if page_ids.is_empty() {
+            if page == 1 && html.contains("This profile is private") {
+                return Err(ScraperError::Private);
+            }
+            break;
+        }
+
+        let known = ids.len();
+        for id in page_ids {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        // Pages past the end may repeat the last page instead of being empty.
+        if ids.len() == known {
+            break;
+        }
+    }
+
+    Ok(ids)
+}
+
+fn parse_shelf_page(document: &Html) -> Result<Vec<String>, ScraperError> {
+    let link_selector = Selector::parse(r#"tr.bookalike td.field.title a[href^="/book/show/"]"#)?;
+
+    let ids = document
+        .select(&link_selector)
+        .filter_map(|link| link.value().attr("href"))
+        .map(extract_goodreads_id)
+        .filter(|id| !id.is_empty())
+        .collect();
+
+    Ok(ids)
+}
+
+pub async fn fetch_editions(
+    client: &GoodreadsClient,
+    work_id: &str,
+) -> Result<Vec<BookEdition>, ScraperError> {
+    let url = format!(
+        "https://www.goodreads.com/work/editions/{}?per_page=100",
+        encode(work_id)
+    );
+    let document = Html::parse_document(&client.get(&url).await?.text());
+    parse_editions(&document)
+}
+
+fn parse_editions(document: &Html) -> Result<Vec<BookEdition>, ScraperError> {
+    let edition_selector = Selector::parse("div.editionData")?;
+    let title_selector = Selector::parse("a.bookTitle")?;
+    let row_selector = Selector::parse("div.dataRow")?;
+    let key_selector = Selector::parse("div.dataTitle")?;
+    let value_selector = Selector::parse("div.dataValue")?;
+
+    let mut editions = Vec::new();
+
+    for edition in document.select(&edition_selector) {
+        let Some(title) = edition.select(&title_selector).next() else {
+            continue;
+        };
+
+        let Some(link) = title.value().attr("href") else {
+            continue;
+        };
+
+        let mut format = None;
+        let mut isbn = None;
+        let mut language = None;
+        let mut publication_year = None;
+
+        for (index, row) in edition.select(&row_selector).enumerate() {
+            let Some(key) = row.select(&key_selector).next() else {
+                let text = row.text().collect::<String>();
+                if let Some(captures) = EDITION_PUBLISHED_RE.captures(&text) {
+                    publication_year = publication_year.or_else(|| captures[1].parse().ok());
+                } else if index == 1 {
+                    format = row
+                        .text()
+                        .collect::<String>()
+                        .split(',')
+                        .next()
+                        .map(|f| f.trim().to_string())
+                        .filter(|f| !f.is_empty());
+                }
+                continue;
+            };
+
+            let value = row
+                .select(&value_selector)
+                .next()
+                .map(|v| v.text().collect::<String>().trim().to_string());
+
+            match key.text().collect::<String>().trim() {
+                "ISBN:" => {
+                    isbn = value.and_then(|v| v.split_whitespace().next().map(str::to_string));
+                }
+                "Edition language:" => language = value.filter(|v| !v.is_empty()),
+                _ => (),
+            }
+        }
+
+        let mut book_edition = BookEdition::new(
+            extract_goodreads_id(link),
+            title.text().collect::<String>().trim().to_string(),
+            format,
+            isbn,
+            language,
+        );
+        book_edition.publication_year = publication_year;
+        editions.push(book_edition);
+    }
+
+    Ok(editions)
+}
+
 fn matches(str1: &str, str2: &str) -> bool {
-    let str1 = str1
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect::<String>();
-    let str2 = str2
-        .chars()
-        .filter(|c| c.is_alphanumeric())
-        .collect::<String>();
+    normalize(str1).contains(&normalize(str2))
+}
+
+/// Scores how closely `result` matches the query, from 0.0 to 1.0.
+///
+/// Each matched string scores the share of it covered by the query, so an exact title scores 1.0 and
+/// "Dracula" against "Dracula's Guest and Other Weird Stories" scores much lower. With an author, the
+/// best-matching listed author is scored the same way and multiplied in.
+pub fn match_confidence(result: &SearchResult, title: &str, author: Option<&str>) -> f32 {
+    let title_score = similarity(strip_series_suffix(&result.title), title);
+    let Some(author) = author else {
+        return title_score;
+    };
+
+    let author_score = result
+        .authors
+        .split(", ")
+        .map(|name| similarity(name, author))
+        .fold(0.0, f32::max);
+
+    title_score * author_score
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn similarity(found: &str, query: &str) -> f32 {
+    let found = normalize(found);
+    let query = normalize(query);
+    if found.is_empty() || !found.contains(&query) {
+        return 0.0;
+    }
 
-    str1.to_lowercase().contains(&str2.to_lowercase())
+    query.chars().count() as f32 / found.chars().count() as f32
+}
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
 }
 
 fn extract_goodreads_id(url: &str) -> String {
@@ -133,12 +871,354 @@ fn extract_goodreads_id(url: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::{HttpFetcher, HttpResponse};
+    use futures::future::BoxFuture;
+
+    #[test]
+    fn title_only_candidate_test() {
+        let result = |title: &str, id: &str| {
+            SearchResult::new(
+                title.to_string(),
+                "Rebecca F. Kuang".to_string(),
+                id.to_string(),
+                None,
+                None,
+                None,
+            )
+        };
+        let options = SearchOptions::default();
+
+        let results = [
+            result("The Poppy War (The Poppy War, #1)", "1"),
+            result("The Poppy War Collection", "2"),
+        ];
+        assert_eq!(
+            title_only_candidate(&results, "The Poppy War", &options)
+                .map(|r| r.goodreads_id.as_str()),
+            Some("1")
+        );
+
+        let results = [result("Babel", "1"), result("Babel", "2")];
+        assert!(title_only_candidate(&results, "Babel", &options).is_none());
+    }
+
+    #[test]
+    fn match_confidence_test() {
+        let result = |title: &str, authors: &str| {
+            SearchResult::new(
+                title.to_string(),
+                authors.to_string(),
+                "1".to_string(),
+                None,
+                None,
+                None,
+            )
+        };
+
+        let exact = result("Dracula", "Bram Stoker");
+        assert!((match_confidence(&exact, "dracula", None) - 1.0).abs() < f32::EPSILON);
+        assert!(
+            (match_confidence(&exact, "Dracula", Some("Bram Stoker")) - 1.0).abs() < f32::EPSILON
+        );
+
+        let partial = result(
+            "Dracula's Guest and Other Weird Stories",
+            "Bram Stoker, Kate Hebblethwaite",
+        );
+        let score = match_confidence(&partial, "Dracula", Some("Stoker"));
+        assert!(score > 0.0 && score < 0.5);
+
+        let series = result(
+            "The Last Olympian (Percy Jackson and the Olympians, #5)",
+            "Rick Riordan",
+        );
+        assert!(
+            (match_confidence(&series, "The Last Olympian", Some("Rick Riordan")) - 1.0).abs()
+                < f32::EPSILON
+        );
+
+        assert!(match_confidence(&exact, "Frankenstein", None).abs() < f32::EPSILON);
+        assert!(match_confidence(&exact, "Dracula", Some("Mary Shelley")).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn parse_shelf_page_test() {
+        let document = Html::parse_document(
+            r#"<table><tbody id="booksBody">
+                <tr class="bookalike review"><td class="field title"><div class="value">
+                    <a title="The Last Olympian" href="/book/show/4556058-the-last-olympian">The Last Olympian</a>
+                </div></td></tr>
+                <tr class="bookalike review"><td class="field title"><div class="value">
+                    <a href="/book/show/30312855?from_search=true">The Last Magician</a>
+                </div></td></tr>
+            </tbody></table>"#,
+        );
+
+        assert_eq!(
+            parse_shelf_page(&document).unwrap(),
+            vec!["4556058".to_string(), "30312855".to_string()]
+        );
+        assert!(
+            parse_shelf_page(&Html::parse_document(""))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn parse_editions_test() {
+        let document = Html::parse_document(
+            r#"<div class="editionData">
+                <div class="dataRow"><a class="bookTitle" href="/book/show/4556058-the-last-olympian">The Last Olympian</a></div>
+                <div class="dataRow">Hardcover, 381 pages</div>
+                <div class="dataRow"><div class="dataTitle">ISBN:</div><div class="dataValue">1423101472 (ISBN13: 9781423101475)</div></div>
+                <div class="dataRow"><div class="dataTitle">Edition language:</div><div class="dataValue">English</div></div>
+            </div>
+            <div class="editionData">
+                <div class="dataRow"><a class="bookTitle" href="/book/show/6466949-the-last-olympian">The Last Olympian</a></div>
+                <div class="dataRow">Paperback, 400 pages</div>
+                <div class="dataRow">Published April 5th 2011 by Disney-Hyperion Books <span class="greyText">(first published 2009)</span></div>
+            </div>
+            <div class="editionData">
+                <div class="dataRow"><a class="bookTitle" href="/book/show/12345-the-last-olympian">The Last Olympian</a></div>
+                <div class="dataRow">Audiobook</div>
+                <div class="dataRow"><span class="greyText">(first published 2009)</span></div>
+            </div>"#,
+        );
+
+        let expected = BookEdition::new(
+            "4556058".to_string(),
+            "The Last Olympian".to_string(),
+            Some("Hardcover".to_string()),
+            Some("1423101472".to_string()),
+            Some("English".to_string()),
+        );
+        let mut paperback = BookEdition::new(
+            "6466949".to_string(),
+            "The Last Olympian".to_string(),
+            Some("Paperback".to_string()),
+            None,
+            None,
+        );
+        paperback.publication_year = Some(2011);
+        let audiobook = BookEdition::new(
+            "12345".to_string(),
+            "The Last Olympian".to_string(),
+            Some("Audiobook".to_string()),
+            None,
+            None,
+        );
+        assert_eq!(
+            parse_editions(&document).unwrap(),
+            vec![expected, paperback, audiobook]
+        );
+    }
+
+    #[test]
+    fn select_candidate_prefers_year_test() {
+        let results = dracula_results();
+
+        let options = SearchOptions {
+            year: Some(2003),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Dracula", Some("Stoker"), &options),
+            Some(&results[1])
+        );
+
+        let options = SearchOptions {
+            year: Some(1950),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Dracula", None, &options),
+            Some(&results[0])
+        );
+    }
+
+    #[test]
+    fn select_candidate_prefers_series_test() {
+        let result = |title: &str, id: &str| {
+            SearchResult::new(
+                title.to_string(),
+                "Various".to_string(),
+                id.to_string(),
+                Some(2010),
+                None,
+                None,
+            )
+        };
+        let results = [
+            result("Shadow (Secret Circle, #2)", "1"),
+            result("Shadow (The Shadow Series, #1)", "2"),
+            result("Shadow", "3"),
+        ];
+
+        let options = SearchOptions {
+            series: Some("The Shadow Series".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Shadow", None, &options),
+            Some(&results[1])
+        );
+
+        let options = SearchOptions {
+            series: Some("Wheel of Time".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Shadow", None, &options),
+            Some(&results[0])
+        );
+
+        assert_eq!(
+            series_name("The Last Olympian (Percy Jackson and the Olympians, #5)"),
+            Some("Percy Jackson and the Olympians")
+        );
+        assert_eq!(series_name("Dracula"), None);
+    }
+
+    #[test]
+    fn select_candidate_rating_thresholds_test() {
+        let results = dracula_results();
+
+        let options = SearchOptions {
+            min_ratings_count: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Dracula", None, &options),
+            Some(&results[1])
+        );
+
+        let options = SearchOptions {
+            min_rating: Some(4.5),
+            ..Default::default()
+        };
+        assert_eq!(select_candidate(&results, "Dracula", None, &options), None);
+    }
+
+    #[test]
+    fn select_candidate_max_candidates_test() {
+        let results = dracula_results();
+
+        let options = SearchOptions {
+            max_candidates: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Dracula", None, &options),
+            Some(&results[0])
+        );
+
+        let options = SearchOptions {
+            max_candidates: Some(1),
+            min_ratings_count: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(select_candidate(&results, "Dracula", None, &options), None);
+    }
+
+    #[test]
+    fn select_candidate_custom_matcher_test() {
+        let results = dracula_results();
+
+        let options = SearchOptions {
+            matcher: Some(Arc::new(|found_title, found_author, title, author| {
+                found_title == title && author.is_some_and(|a| found_author.ends_with(a))
+            })),
+            year: Some(2003),
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "Dracula", Some("Stoker"), &options),
+            Some(&results[1])
+        );
+        assert_eq!(
+            select_candidate(&results, "Dracula", Some("Bram"), &options),
+            None
+        );
+        assert_eq!(title_only_candidate(&results, "Dracula", &options), None);
+    }
+
+    #[test]
+    fn parse_rating_stats_test() {
+        let text = "Dracula by Bram Stoker 4.01 avg rating — 1,234,567 ratings — published 1897";
+        assert_eq!(parse_rating_stats(text), (Some(4.01), Some(1_234_567)));
+        assert_eq!(parse_rating_stats("no stats"), (None, None));
+    }
+
+    #[test]
+    fn select_candidate_exact_title_test() {
+        let result = |title: &str, id: &str| {
+            SearchResult::new(
+                title.to_string(),
+                "Stephen King".to_string(),
+                id.to_string(),
+                None,
+                None,
+                None,
+            )
+        };
+        let results = vec![
+            result("The Institute", "43798285"),
+            result("It", "830502"),
+            result("The Shining (The Shining, #1)", "11588"),
+        ];
+
+        let options = SearchOptions {
+            exact_title: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            select_candidate(&results, "It", None, &SearchOptions::default()),
+            Some(&results[0])
+        );
+        assert_eq!(
+            select_candidate(&results, "It", None, &options),
+            Some(&results[1])
+        );
+        assert_eq!(
+            select_candidate(&results, "the shining", Some("King"), &options),
+            Some(&results[2])
+        );
+        assert_eq!(select_candidate(&results, "Shining", None, &options), None);
+    }
+
+    fn dracula_results() -> Vec<SearchResult> {
+        vec![
+            SearchResult::new(
+                "Dracula".to_string(),
+                "Bram Stoker".to_string(),
+                "17245".to_string(),
+                Some(1897),
+                Some(4.0),
+                Some(2),
+            ),
+            SearchResult::new(
+                "Dracula".to_string(),
+                "Bram Stoker".to_string(),
+                "3141".to_string(),
+                Some(2003),
+                Some(4.1),
+                Some(1_234_567),
+            ),
+        ]
+    }
 
     #[tokio::test]
     async fn fetch_id_from_title_test() {
         let book_title = "The Last Magician";
         assert_eq!(
-            fetch_id_from_title(book_title).await.unwrap(),
+            fetch_id_from_title(
+                &GoodreadsClient::default(),
+                book_title,
+                &SearchOptions::default()
+            )
+            .await
+            .unwrap(),
             Some("30312855".to_string())
         );
     }
@@ -146,7 +1226,16 @@ mod tests {
     #[tokio::test]
     async fn fetch_id_from_title_not_found_test() {
         let book_title = "thistitledoesnotexist";
-        assert_eq!(fetch_id_from_title(book_title).await.unwrap(), None);
+        assert_eq!(
+            fetch_id_from_title(
+                &GoodreadsClient::default(),
+                book_title,
+                &SearchOptions::default()
+            )
+            .await
+            .unwrap(),
+            None
+        );
     }
 
     #[tokio::test]
@@ -154,9 +1243,14 @@ mod tests {
         let book_title = "Fire";
         let book_author = "Kristin Cashore";
         assert_eq!(
-            fetch_id_from_title_and_author(book_title, book_author)
-                .await
-                .unwrap(),
+            fetch_id_from_title_and_author(
+                &GoodreadsClient::default(),
+                book_title,
+                book_author,
+                &SearchOptions::default()
+            )
+            .await
+            .unwrap(),
             Some("6137154".to_string())
         );
     }
@@ -166,18 +1260,93 @@ mod tests {
         let book_title = "thistitledoesnotexist";
         let book_author = "noauthor";
         assert_eq!(
-            fetch_id_from_title_and_author(book_title, book_author)
-                .await
-                .unwrap(),
+            fetch_id_from_title_and_author(
+                &GoodreadsClient::default(),
+                book_title,
+                book_author,
+                &SearchOptions::default()
+            )
+            .await
+            .unwrap(),
             None
         );
     }
 
+    #[tokio::test]
+    async fn search_title_no_results_test() {
+        let outcome = search_title(
+            &GoodreadsClient::default(),
+            "thistitledoesnotexist",
+            &SearchOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, SearchOutcome::NoResults);
+    }
+
+    #[tokio::test]
+    async fn search_title_and_author_no_match_test() {
+        let outcome = search_title_and_author(
+            &GoodreadsClient::default(),
+            "Fire",
+            "noauthor",
+            &SearchOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(outcome, SearchOutcome::NoMatch { candidates } if !candidates.is_empty()));
+    }
+
+    #[test]
+    fn parse_isbn_search_test() {
+        let redirected = r#"<script id="__NEXT_DATA__" type="application/json">
+            {"props":{"pageProps":{"params":{"book_id":"4556058-the-last-olympian"}}}}
+        </script>"#;
+        assert_eq!(parse_isbn_search(redirected).unwrap(), vec!["4556058"]);
+
+        let disambiguation = r#"<script id="__NEXT_DATA__" type="application/json">{"props":{"pageProps":{}}}</script>
+            <table class="tableList">
+                <tr itemscope itemtype="http://schema.org/Book">
+                    <td><a class="bookTitle" href="/book/show/4556058-the-last-olympian">The Last Olympian</a></td>
+                </tr>
+                <tr itemscope itemtype="http://schema.org/Book">
+                    <td><a class="bookTitle" href="/book/show/7123440-the-last-olympian">The Last Olympian</a></td>
+                </tr>
+            </table>"#;
+        assert_eq!(
+            parse_isbn_search(disambiguation).unwrap(),
+            vec!["4556058", "7123440"]
+        );
+
+        assert!(
+            parse_isbn_search("<html><body>No results.</body></html>")
+                .unwrap()
+                .is_empty()
+        );
+
+        assert_eq!(
+            parse_asin_search(redirected).unwrap().as_deref(),
+            Some("4556058")
+        );
+        assert_eq!(parse_asin_search(disambiguation).unwrap(), None);
+        let single = r#"<table class="tableList">
+                <tr itemscope itemtype="http://schema.org/Book">
+                    <td><a class="bookTitle" href="/book/show/7123440-the-last-olympian">The Last Olympian</a></td>
+                </tr>
+            </table>"#;
+        assert_eq!(
+            parse_asin_search(single).unwrap().as_deref(),
+            Some("7123440")
+        );
+    }
+
     #[tokio::test]
     async fn fetch_id_from_isbn_test() {
         let isbn = "9780063021426";
         assert_eq!(
-            fetch_id_from_isbn(isbn).await.unwrap(),
+            fetch_id_from_isbn(&GoodreadsClient::default(), isbn)
+                .await
+                .unwrap(),
             Some("57945316".to_string())
         );
     }
@@ -185,18 +1354,160 @@ mod tests {
     #[tokio::test]
     async fn fetch_id_from_isbn_not_found_test() {
         let isbn = "1234001592323";
-        assert_eq!(fetch_id_from_isbn(isbn).await.unwrap(), None);
+        assert_eq!(
+            fetch_id_from_isbn(&GoodreadsClient::default(), isbn)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn search_author_test() {
+        let results = search_author(&GoodreadsClient::default(), "Lisa Maxwell")
+            .await
+            .unwrap();
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.authors.contains("Lisa Maxwell")));
+    }
+
+    #[tokio::test]
+    async fn fetch_id_from_work_id_test() {
+        assert_eq!(
+            fetch_id_from_work_id(&GoodreadsClient::default(), "4551489")
+                .await
+                .unwrap(),
+            Some("4556058".to_string())
+        );
+    }
+
+    // Answers every request as if it had been redirected to `self.0`.
+    struct RedirectFetcher(&'static str);
+
+    impl HttpFetcher for RedirectFetcher {
+        fn get<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<HttpResponse, ScraperError>> {
+            Box::pin(async move { Ok(HttpResponse::new(self.0, "<html></html>")) })
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_id_from_work_id_redirect_test() {
+        let client = GoodreadsClient::from_fetcher(RedirectFetcher(
+            "https://www.goodreads.com/book/show/4556058-the-last-olympian?from=work",
+        ));
+        assert_eq!(
+            fetch_id_from_work_id(&client, "4551489").await.unwrap(),
+            Some("4556058".to_string())
+        );
+
+        let client = GoodreadsClient::from_fetcher(RedirectFetcher(
+            "https://www.goodreads.com/work/best_book/4551489",
+        ));
+        assert_eq!(
+            fetch_id_from_work_id(&client, "4551489").await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_id_from_asin_test() {
+        let asin = "b00eo2wmfw";
+        assert!(
+            fetch_id_from_asin(&GoodreadsClient::default(), asin)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn parse_author_list_test() {
+        let html = r#"<table class="tableList">
+            <tr itemscope itemtype="http://schema.org/Book">
+                <td><a class="bookTitle" href="/book/show/4556058-the-last-olympian"><span itemprop="name">The Last Olympian (Percy Jackson and the Olympians, #5)</span></a>
+                <span itemprop="author"><a class="authorName" href="/author/show/15872.Rick_Riordan"><span itemprop="name">Rick Riordan</span></a></span>
+                <span class="greyText smallText uitext">4.49 avg rating &mdash; 1,001,562 ratings &mdash; published 2009</span></td>
+            </tr>
+        </table>"#;
+
+        let expected = SearchResult::new(
+            "The Last Olympian (Percy Jackson and the Olympians, #5)".to_string(),
+            "Rick Riordan".to_string(),
+            "4556058".to_string(),
+            Some(2009),
+            Some(4.49),
+            Some(1_001_562),
+        );
+        assert_eq!(parse_search_results(html).unwrap(), vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn search_author_books_test() {
+        let outcome = search_author_books(
+            &GoodreadsClient::default(),
+            "15872",
+            "The Last Olympian",
+            &SearchOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.goodreads_id(), Some("4556058"));
+    }
+
+    #[tokio::test]
+    async fn search_books_test() {
+        let results = search_books(&GoodreadsClient::default(), "The Last Olympian")
+            .await
+            .unwrap();
+        assert!(results.iter().any(|r| r.goodreads_id == "4556058"));
+    }
+
+    #[tokio::test]
+    async fn search_stream_test() {
+        let results = search_stream(GoodreadsClient::default(), "Dracula", 2)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(results.len() > 20);
+        assert!(results.iter().all(Result::is_ok));
+    }
+
+    #[tokio::test]
+    async fn verify_isbn_exists_test() {
+        let isbn = "9780063021426";
+        assert!(
+            verify_isbn_exists(&GoodreadsClient::default(), isbn)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_isbn_not_found_test() {
+        let isbn = "1234001592323";
+        assert!(
+            !verify_isbn_exists(&GoodreadsClient::default(), isbn)
+                .await
+                .unwrap()
+        );
     }
 
     #[tokio::test]
     async fn verify_id_exists_test() {
-        let id = "57945316";
-        assert!(verify_id_exists(id).await);
+        let id = &GoodreadsId::from(57_945_316);
+        assert!(
+            verify_id_exists(&GoodreadsClient::default(), id)
+                .await
+                .unwrap()
+        );
     }
 
     #[tokio::test]
     async fn verify_id_not_found_test() {
-        let id = "bad_id";
-        assert!(!(verify_id_exists(id).await));
+        let id = &GoodreadsId::from(999_999_999_999);
+        assert!(
+            !(verify_id_exists(&GoodreadsClient::default(), id)
+                .await
+                .unwrap())
+        );
     }
 }
@@ -0,0 +1,184 @@
+use crate::errors::ScraperError;
+use log::warn;
+#[cfg(feature = "net")]
+use {
+    chrono::{DateTime, Utc},
+    futures::future::BoxFuture,
+    reqwest::{Client, StatusCode, header::RETRY_AFTER},
+    std::time::Duration,
+};
+
+const CHALLENGE_MARKERS: [&str; 4] = [
+    "captcha",
+    "challenge.js",
+    "<title>robot check</title>",
+    "automated access",
+];
+
+/// The response to a successful GET request, as returned by an `HttpFetcher`.
+#[cfg(feature = "net")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    /// The final URL of the response, after following redirects. Lookups such as ISBN searches rely on
+    /// Goodreads redirecting to the matching book page.
+    pub url: String,
+    /// The raw response body.
+    pub body: Vec<u8>,
+}
+
+#[cfg(feature = "net")]
+impl HttpResponse {
+    pub fn new(url: impl Into<String>, body: impl Into<Vec<u8>>) -> Self {
+        HttpResponse {
+            url: url.into(),
+            body: body.into(),
+        }
+    }
+
+    /// The body decoded as UTF-8, replacing invalid sequences.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    // The Goodreads ID of the book page the request ended on, e.g. "4556058" for "/book/show/4556058-the-last-olympian".
+    pub(crate) fn book_id(&self) -> Option<String> {
+        let id = self
+            .path()
+            .strip_prefix("/book/show/")?
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        Some(id).filter(|id| !id.is_empty())
+    }
+
+    // The path of the final URL, without the query or fragment, e.g. "/book/show/4556058".
+    pub(crate) fn path(&self) -> &str {
+        let url = self
+            .url
+            .split_once("://")
+            .map_or(&*self.url, |(_, rest)| rest);
+        let path = url.find('/').map_or("", |start| &url[start..]);
+        path.split(['?', '#']).next().unwrap_or(path)
+    }
+}
+
+/// The transport a `GoodreadsClient` sends its GET requests through, e.g. to route them through a custom
+/// HTTP stack or to serve canned pages in tests. `reqwest::Client` implements it and is the default.
+///
+/// Implementations must follow redirects and report the final URL, and should fail with
+/// `ScraperError::HttpStatus` for unsuccessful status codes (`ScraperError::RateLimited` for 429), since
+/// lookups tell missing books apart by their 404 responses. The returned future is usually built with
+/// `Box::pin(async move { ... })`.
+#[cfg(feature = "net")]
+pub trait HttpFetcher: Send + Sync {
+    fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<HttpResponse, ScraperError>>;
+}
+
+#[cfg(feature = "net")]
+impl HttpFetcher for Client {
+    fn get<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<HttpResponse, ScraperError>> {
+        Box::pin(get(self, url))
+    }
+}
+
+#[cfg(feature = "net")]
+async fn get(client: &Client, url: &str) -> Result<HttpResponse, ScraperError> {
+    let response = client.get(url).send().await?;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+
+        warn!("Rate limited by Goodreads");
+        return Err(ScraperError::RateLimited { retry_after });
+    }
+
+    if !response.status().is_success() {
+        warn!("Goodreads responded with {} for {url}", response.status());
+        return Err(ScraperError::HttpStatus(response.status().as_u16()));
+    }
+
+    let url = response.url().to_string();
+    let body = response.bytes().await?.to_vec();
+    Ok(HttpResponse { url, body })
+}
+
+/// Returns `ScraperError::Blocked` if `html` looks like an anti-bot challenge or CAPTCHA page.
+/// Only meant to be called once the expected content was found to be missing.
+pub fn check_challenge(html: &str) -> Result<(), ScraperError> {
+    let html = html.to_lowercase();
+    let blocked = CHALLENGE_MARKERS.iter().any(|marker| html.contains(marker));
+
+    if blocked {
+        warn!("Blocked by a Goodreads challenge page");
+        return Err(ScraperError::Blocked);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "net")]
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?.to_utc();
+    let delay = date.signed_duration_since(Utc::now());
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_challenge_test() {
+        let challenge = r#"<html><head><script src="https://x.token.awswaf.com/challenge.js"></script></head></html>"#;
+        assert!(matches!(
+            check_challenge(challenge),
+            Err(ScraperError::Blocked)
+        ));
+        assert!(check_challenge("<html><body>Page not found</body></html>").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn http_response_path_test() {
+        let response = HttpResponse::new(
+            "https://www.goodreads.com/book/show/4556058?from_search=true#top",
+            "",
+        );
+        assert_eq!(response.path(), "/book/show/4556058");
+        assert_eq!(response.book_id().as_deref(), Some("4556058"));
+        assert_eq!(
+            HttpResponse::new("https://www.goodreads.com", "").path(),
+            ""
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_retry_after_seconds_test() {
+        assert_eq!(parse_retry_after("90"), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_retry_after_past_date_test() {
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_retry_after_invalid_test() {
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
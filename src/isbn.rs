@@ -0,0 +1,164 @@
+use crate::errors::ScraperError;
+use std::fmt;
+
+/// A validated ISBN-10 or ISBN-13, stored without separators.
+///
+/// `Isbn::parse` accepts hyphens and spaces (e.g. "978-0-14-138147-3") and a lowercase `x` check digit,
+/// and rejects anything whose length or checksum is wrong.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Isbn(String);
+
+impl Isbn {
+    pub fn parse(isbn: &str) -> Result<Self, ScraperError> {
+        let normalized: String = isbn
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' '))
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+
+        // Checked first, so the slicing below stays on character boundaries.
+        let ascii = normalized.chars().all(|c| c.is_ascii_digit() || c == 'X');
+        let valid = match normalized.len() {
+            _ if !ascii => false,
+            10 => isbn10_check_digit(&normalized[..9]) == normalized[9..].chars().next(),
+            13 => isbn13_check_digit(&normalized[..12]) == normalized[12..].chars().next(),
+            _ => false,
+        };
+
+        if !valid {
+            return Err(ScraperError::ParseError(format!("Invalid ISBN {isbn}")));
+        }
+
+        Ok(Isbn(normalized))
+    }
+
+    /// The ISBN without separators, as parsed.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether this is a 10-digit ISBN.
+    pub fn is_isbn10(&self) -> bool {
+        self.0.len() == 10
+    }
+
+    /// The ISBN-13 form, prefixing ISBN-10s with "978" and recomputing the check digit.
+    pub fn as_isbn13(&self) -> String {
+        if !self.is_isbn10() {
+            return self.0.clone();
+        }
+
+        let body = format!("978{}", &self.0[..9]);
+        match isbn13_check_digit(&body) {
+            Some(check) => format!("{body}{check}"),
+            None => self.0.clone(),
+        }
+    }
+
+    /// The ISBN-10 form, only available for ISBN-13s with the "978" prefix.
+    pub fn as_isbn10(&self) -> Option<String> {
+        if self.is_isbn10() {
+            return Some(self.0.clone());
+        }
+
+        let body = self.0.strip_prefix("978")?.get(..9)?;
+        let check = isbn10_check_digit(body)?;
+        Some(format!("{body}{check}"))
+    }
+}
+
+impl fmt::Display for Isbn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Isbn {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for Isbn {
+    type Error = ScraperError;
+
+    fn try_from(isbn: &str) -> Result<Self, Self::Error> {
+        Isbn::parse(isbn)
+    }
+}
+
+impl TryFrom<String> for Isbn {
+    type Error = ScraperError;
+
+    fn try_from(isbn: String) -> Result<Self, Self::Error> {
+        Isbn::parse(&isbn)
+    }
+}
+
+fn digits(body: &str) -> Option<Vec<u32>> {
+    body.chars().map(|c| c.to_digit(10)).collect()
+}
+
+fn isbn10_check_digit(body: &str) -> Option<char> {
+    let sum: u32 = digits(body)?
+        .iter()
+        .zip((2..=10).rev())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+
+    match (11 - sum % 11) % 11 {
+        10 => Some('X'),
+        check => char::from_digit(check, 10),
+    }
+}
+
+fn isbn13_check_digit(body: &str) -> Option<char> {
+    let sum: u32 = digits(body)?
+        .iter()
+        .zip([1, 3].into_iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+
+    char::from_digit((10 - sum % 10) % 10, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_isbn_test() {
+        let isbn = Isbn::parse("978-0-14-138147-3").unwrap();
+        assert_eq!(isbn.as_str(), "9780141381473");
+        assert!(!isbn.is_isbn10());
+
+        let isbn = Isbn::parse("0 8044 2957 x").unwrap();
+        assert_eq!(isbn.to_string(), "080442957X");
+        assert!(isbn.is_isbn10());
+
+        assert!(matches!(
+            Isbn::parse("1234001592323"),
+            Err(ScraperError::ParseError(_))
+        ));
+        assert!(Isbn::parse("142310147").is_err());
+        assert!(Isbn::parse("14231014X2").is_err());
+        assert!(Isbn::try_from("1423101472").is_ok());
+
+        assert!(Isbn::parse("12345678é").is_err());
+        assert!(Isbn::parse("12345678901é").is_err());
+    }
+
+    #[test]
+    fn convert_isbn_test() {
+        let isbn10 = Isbn::parse("1423101472").unwrap();
+        assert_eq!(isbn10.as_isbn13(), "9781423101475");
+        assert_eq!(isbn10.as_isbn10().as_deref(), Some("1423101472"));
+
+        let isbn13 = Isbn::parse("9780804429573").unwrap();
+        assert_eq!(isbn13.as_isbn10().as_deref(), Some("080442957X"));
+        assert_eq!(isbn13.as_isbn13(), "9780804429573");
+
+        let isbn13 = Isbn::parse("9791032305690").unwrap();
+        assert_eq!(isbn13.as_isbn10(), None);
+    }
+}
@@ -33,10 +33,11 @@
 //! ### Fetching Metadata by ISBN
 //!
 //! ```rust
-//! use grscraper::MetadataRequestBuilder;
-//!
+//! # #[cfg(feature = "net")]
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), grscraper::ScraperError> {
+//! use grscraper::MetadataRequestBuilder;
+//!
 //! let isbn = "9780141381473";
 //! let metadata = MetadataRequestBuilder::default()
 //!     .with_isbn(isbn)
@@ -48,15 +49,18 @@
 //! println!("{:#?}", metadata);
 //! # Ok::<(), grscraper::ScraperError>(())
 //! # }
+//! # #[cfg(not(feature = "net"))]
+//! # fn main() {}
 //! ```
 //!
 //! ### Fetching Metadata by Goodreads ID
 //!
 //! ```rust
-//! use grscraper::MetadataRequestBuilder;
-//!
+//! # #[cfg(feature = "net")]
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), grscraper::ScraperError> {
+//! use grscraper::MetadataRequestBuilder;
+//!
 //! let goodreads_id = "175254";
 //! let metadata = MetadataRequestBuilder::default()
 //!     .with_id(goodreads_id)
@@ -68,6 +72,8 @@
 //! println!("{:#?}", metadata);
 //! # Ok::<(), grscraper::ScraperError>(())
 //! # }
+//! # #[cfg(not(feature = "net"))]
+//! # fn main() {}
 //! ```
 //!
 //! ### Fetching Metadata by Title and Author
@@ -75,10 +81,11 @@
 //! Providing an author along with the title helps improve the accuracy of the search:
 //!
 //! ```rust
-//! use grscraper::MetadataRequestBuilder;
-//!
+//! # #[cfg(feature = "net")]
 //! # #[tokio::main]
 //! # async fn main() -> Result<(), grscraper::ScraperError> {
+//! use grscraper::MetadataRequestBuilder;
+//!
 //! let title = "The Last Magician";
 //! let author = "Lisa Maxwell";
 //! let metadata = MetadataRequestBuilder::default()
@@ -92,6 +99,57 @@
 //! println!("{:#?}", metadata);
 //! # Ok::<(), grscraper::ScraperError>(())
 //! # }
+//! # #[cfg(not(feature = "net"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### Reusing a Client
+//!
+//! Each `MetadataRequestBuilder::default()` creates its own HTTP client. For many lookups, create a
+//! `GoodreadsClient` once and reuse it, so connections are pooled across requests:
+//!
+//! ```rust
+//! # #[cfg(feature = "net")]
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), grscraper::ScraperError> {
+//! use grscraper::GoodreadsClient;
+//! use std::time::Duration;
+//!
+//! let client = GoodreadsClient::builder()
+//!     .with_timeout(Duration::from_secs(30))
+//!     .build()?;
+//!
+//! let metadata = client
+//!     .metadata_by_isbn("9780141381473")
+//!     .await?
+//!     .expect("Book not found");
+//!
+//! assert_eq!(metadata.title, "The Lightning Thief");
+//! # Ok::<(), grscraper::ScraperError>(())
+//! # }
+//! # #[cfg(not(feature = "net"))]
+//! # fn main() {}
+//! ```
+//!
+//! ### Parsing Downloaded Pages
+//!
+//! The networking layer is behind the default `net` feature. Without it, `reqwest` is not compiled and
+//! already downloaded book pages can still be parsed:
+//!
+//! ```toml
+//! [dependencies]
+//! goodreads-metadata-scraper = { version = "0.2.5", default-features = false }
+//! ```
+//!
+//! ```rust,no_run
+//! use grscraper::BookMetadata;
+//!
+//! # fn main() -> Result<(), grscraper::ScraperError> {
+//! let html = std::fs::read_to_string("4556058.html").expect("Page must be readable");
+//! let metadata = BookMetadata::from_html(&html, "4556058")?;
+//! println!("{:#?}", metadata);
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! ## Limitations
@@ -103,13 +161,44 @@
 //!
 //!
 
+#[cfg(feature = "net")]
+mod client;
 mod errors;
+mod goodreads_id;
+#[cfg(feature = "net")]
 mod goodreads_id_fetcher;
+mod http;
+mod isbn;
 mod metadata_fetcher;
+#[cfg(feature = "opds")]
+mod opds;
+#[cfg(feature = "net")]
 mod request_builder;
 
+#[cfg(feature = "net")]
+pub use client::GoodreadsClient;
+#[cfg(feature = "net")]
+pub use client::GoodreadsClientBuilder;
 pub use errors::ScraperError;
+pub use goodreads_id::GoodreadsId;
+#[cfg(feature = "net")]
+pub use goodreads_id_fetcher::BookEdition;
+#[cfg(feature = "net")]
+pub use goodreads_id_fetcher::SearchOutcome;
+#[cfg(feature = "net")]
+pub use goodreads_id_fetcher::SearchResult;
+#[cfg(feature = "net")]
+pub use http::{HttpFetcher, HttpResponse};
+pub use isbn::Isbn;
 pub use metadata_fetcher::BookContributor;
 pub use metadata_fetcher::BookMetadata;
 pub use metadata_fetcher::BookSeries;
+pub use metadata_fetcher::ContributorRole;
+pub use metadata_fetcher::DEFAULT_GENRE_BLOCKLIST;
+pub use metadata_fetcher::MetadataField;
+#[cfg(feature = "net")]
 pub use request_builder::MetadataRequestBuilder;
+#[cfg(feature = "net")]
+pub use request_builder::{by_id, by_isbn, by_title};
+#[cfg(feature = "net")]
+pub use reqwest::Proxy;
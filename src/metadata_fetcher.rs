@@ -1,14 +1,122 @@
-use crate::errors::ScraperError;
-use chrono::{DateTime, Utc};
+#[cfg(feature = "net")]
+use crate::{
+    client::GoodreadsClient,
+    goodreads_id_fetcher::{BookEdition, fetch_editions},
+};
+use crate::{errors::ScraperError, http::check_challenge};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use derive_new::new;
+use html_escape::decode_html_entities;
 use log::{error, warn};
 use regex::Regex;
-use reqwest::get;
 use scraper::{Html, Selector};
 use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fmt,
+    hash::{Hash, Hasher},
+    sync::LazyLock,
+};
+#[cfg(feature = "net")]
+use urlencoding::encode;
+
+// How many times a book page whose page data was cut off is fetched again before giving up.
+#[cfg(feature = "net")]
+const TRUNCATED_PAGE_RETRIES: u32 = 2;
+
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%B %d, %Y", "%b %d, %Y", "%d %B %Y"];
+
+// Goodreads lists languages by their ISO 639-2 English names, some of which carry alternative names.
+const LANGUAGE_CODES: &[(&str, &str)] = &[
+    ("English", "en"),
+    ("Spanish", "es"),
+    ("Spanish; Castilian", "es"),
+    ("French", "fr"),
+    ("German", "de"),
+    ("Italian", "it"),
+    ("Portuguese", "pt"),
+    ("Dutch", "nl"),
+    ("Dutch; Flemish", "nl"),
+    ("Russian", "ru"),
+    ("Japanese", "ja"),
+    ("Chinese", "zh"),
+    ("Korean", "ko"),
+    ("Arabic", "ar"),
+    ("Polish", "pl"),
+    ("Swedish", "sv"),
+    ("Norwegian", "no"),
+    ("Norwegian Bokmål", "nb"),
+    ("Norwegian Nynorsk", "nn"),
+    ("Danish", "da"),
+    ("Finnish", "fi"),
+    ("Turkish", "tr"),
+    ("Greek", "el"),
+    ("Greek, Modern (1453-)", "el"),
+    ("Hebrew", "he"),
+    ("Hindi", "hi"),
+    ("Bengali", "bn"),
+    ("Urdu", "ur"),
+    ("Tamil", "ta"),
+    ("Persian", "fa"),
+    ("Czech", "cs"),
+    ("Slovak", "sk"),
+    ("Slovenian", "sl"),
+    ("Hungarian", "hu"),
+    ("Romanian", "ro"),
+    ("Romanian; Moldavian; Moldovan", "ro"),
+    ("Bulgarian", "bg"),
+    ("Croatian", "hr"),
+    ("Serbian", "sr"),
+    ("Ukrainian", "uk"),
+    ("Lithuanian", "lt"),
+    ("Latvian", "lv"),
+    ("Estonian", "et"),
+    ("Icelandic", "is"),
+    ("Irish", "ga"),
+    ("Welsh", "cy"),
+    ("Catalan", "ca"),
+    ("Catalan; Valencian", "ca"),
+    ("Basque", "eu"),
+    ("Galician", "gl"),
+    ("Albanian", "sq"),
+    ("Afrikaans", "af"),
+    ("Indonesian", "id"),
+    ("Malay", "ms"),
+    ("Vietnamese", "vi"),
+    ("Thai", "th"),
+    ("Tagalog", "tl"),
+    ("Latin", "la"),
+    ("Esperanto", "eo"),
+];
+
+static YEAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(-?\d{1,6})\s*(BCE?|B\.C\.(?:E\.)?)?$").expect("Regex must be valid")
+});
+static IMAGE_TIMESTAMP_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"/\d+i/").expect("Regex must be valid"));
+static DURATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(?:(\d+)\s*(?:hours?|hrs?|h))?(?:\s|,|\band\b)*(?:(\d+)\s*(?:minutes?|mins?|m))?$",
+    )
+    .expect("Regex must be valid")
+});
+static LEGACY_ID_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^getBookByLegacyId\(.*"legacyId"\s*:\s*"?(\d+)"?"#).expect("Regex must be valid")
+});
+static INITIALS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\p{L}\.)+$").expect("Regex must be valid"));
+static WHITESPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s{2,}").expect("Regex must be valid"));
+#[cfg(feature = "net")]
+static COVER_SIZE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\._[A-Z]{2}[0-9A-Z_,]*_(\.\w+)$").expect("Regex must be valid"));
 
 /// The primary data structure containing the metadata of a book.
-#[derive(Debug, new, PartialEq)]
+///
+/// `BookMetadata::with_title` and `Default` build a book with every other field empty, e.g. for tests:
+/// `BookMetadata { page_count: Some(381), ..BookMetadata::with_title("The Last Olympian") }`.
+#[derive(Debug, Clone, Default, new, PartialEq)]
 pub struct BookMetadata {
     /// The main title of the book.
     pub title: String,
@@ -16,28 +124,296 @@ pub struct BookMetadata {
     pub subtitle: Option<String>,
     /// An optional description or summary of the book.
     pub description: Option<String>,
-    /// The publisher of the book, if available.
+    /// The publisher of the book, if available. Goodreads does not list the publisher's country or region.
     pub publisher: Option<String>,
     /// The publication date of the book, represented as a UTC datetime.
+    /// Dates before 1970 and BCE dates are supported, using the proleptic Gregorian calendar where 1 BC is year 0.
     pub publication_date: Option<DateTime<Utc>>,
     /// The ISBN of the book, if available.
     pub isbn: Option<String>,
     /// A list of contributors to the book, each represented as a `BookContributor`.
+    /// Ordered by role (authors, editors, translators, illustrators, then everyone else), with the primary
+    /// contributor first within its role and page order otherwise. Co-authors thus keep their credited order.
     pub contributors: Vec<BookContributor>,
-    /// A list of genres associated with the book.
+    /// A list of genres associated with the book, in Goodreads' ranking order (most voted first).
+    /// The order is kept as served, so it only changes when Goodreads' ranking does.
     pub genres: Vec<String>,
     /// The series information, if the book is part of a series, represented as a `BookSeries`.
     pub series: Option<BookSeries>,
-    /// The number of pages in the book, if available.
+    /// The number of pages in the book, if available. Always `None` for audiobooks, see `duration_minutes`.
+    /// Goodreads does not publish word counts or reading times, so this is the only length information.
     pub page_count: Option<i64>,
     /// The language of the book, if available.
     pub language: Option<String>,
     /// A URL to an image of the book's cover, if available.
     pub image_url: Option<String>,
+    /// The Goodreads ID of the work this edition belongs to, if available.
+    #[new(default)]
+    pub work_id: Option<String>,
+    /// The number of editions of the work listed on Goodreads, if available.
+    #[new(default)]
+    pub edition_count: Option<i64>,
+    /// The most popular user shelves the work was added to, with how many users applied each shelf.
+    /// Only fetched when requested with `MetadataRequestBuilder::with_shelves`, empty otherwise.
+    #[new(default)]
+    pub shelves: Vec<(String, i64)>,
+    /// Whether the description looks cut off by Goodreads, i.e. it ends with an ellipsis.
+    #[new(default)]
+    pub description_truncated: bool,
+    /// The binding or format of the edition, such as "Hardcover" or "Kindle Edition", if available.
+    #[new(default)]
+    pub format: Option<String>,
+    /// The physical dimensions of print editions as listed by Goodreads, if available.
+    #[new(default)]
+    pub dimensions: Option<String>,
+    /// The shipping weight of print editions as listed by Goodreads, if available.
+    #[new(default)]
+    pub weight: Option<String>,
+    /// Goodreads IDs of the "Readers also enjoyed" recommendations embedded in the page, if any.
+    #[new(default)]
+    pub similar_books: Vec<String>,
+    /// The listening time of audiobook editions in minutes, if available.
+    #[new(default)]
+    pub duration_minutes: Option<i64>,
+    /// The publication year of the book, if available. When Goodreads only knows the year, `publication_date`
+    /// holds a January 1st placeholder and this is the meaningful value. Falls back to the work's original
+    /// publication year when the edition has no date.
+    #[new(default)]
+    pub publication_year: Option<i32>,
+    /// The ISBN of the earliest published edition of the work that lists one, for provenance.
+    /// Only fetched when requested with `MetadataRequestBuilder::with_first_edition`, `None` otherwise.
+    #[new(default)]
+    pub first_edition_isbn: Option<String>,
+    /// The description of the work, shared by all its editions, if available. `description` falls back
+    /// to it when the edition has no description of its own.
+    #[new(default)]
+    pub work_description: Option<String>,
+    /// How many users rated the book, as a popularity signal for choosing between editions. Goodreads mostly
+    /// only exposes this for the whole work, in which case all editions of the work report the same count.
+    #[new(default)]
+    pub edition_popularity: Option<i64>,
+    /// How many ratings gave one to five stars, in that order, if available. Like `edition_popularity`, this
+    /// usually covers the whole work.
+    #[new(default)]
+    pub ratings_histogram: Option<[i64; 5]>,
+    /// The canonical Goodreads ID of the scraped edition, if available. It differs from the requested ID when
+    /// Goodreads redirected to another book, e.g. after merging duplicates, so it is the one to deduplicate by.
+    #[new(default)]
+    pub goodreads_id: Option<String>,
+    /// The requested Goodreads ID when Goodreads redirected it to `goodreads_id`, `None` otherwise.
+    #[new(default)]
+    pub redirected_from: Option<String>,
+    /// The publication time exactly as Goodreads stores it, in milliseconds since the Unix epoch, for consumers
+    /// doing their own date handling. `None` when Goodreads stores the date as text, e.g. "1847" or "700 BC".
+    #[new(default)]
+    pub publication_timestamp_ms: Option<i64>,
+    /// The ISO 639-1 code of `language`, such as "en" for "English", if it is a known language. Unknown language
+    /// names are only available through `language`.
+    #[new(default)]
+    pub language_code: Option<String>,
+}
+
+impl BookMetadata {
+    /// A book with the given title and every other field `None`, empty or `false`.
+    pub fn with_title(title: impl Into<String>) -> Self {
+        BookMetadata {
+            title: title.into(),
+            ..BookMetadata::default()
+        }
+    }
+
+    /// Compares two books like `==`, but ignores the cache-busting timestamp segment of `image_url`
+    /// (e.g. `/1723393514i/`), which changes whenever Goodreads re-processes a cover.
+    pub fn content_eq(&self, other: &BookMetadata) -> bool {
+        let normalize = |metadata: &BookMetadata| BookMetadata {
+            image_url: metadata
+                .image_url
+                .as_ref()
+                .map(|url| IMAGE_TIMESTAMP_RE.replace(url, "/").into_owned()),
+            ..metadata.clone()
+        };
+
+        normalize(self) == normalize(other)
+    }
+
+    /// Parses the metadata from the HTML of an already downloaded Goodreads book page
+    /// (`https://www.goodreads.com/book/show/{goodreads_id}`), without any network access.
+    /// `shelves` is left empty, since shelves live on a separate page.
+    pub fn from_html(html: &str, goodreads_id: &str) -> Result<Self, ScraperError> {
+        let metadata = extract_book_metadata(html)?;
+        parse_metadata(&metadata, goodreads_id, &ExtractOptions::default())
+    }
+
+    /// Re-fetches this book by its `goodreads_id` with a default client, e.g. to keep a local catalog up to date.
+    /// Use `GoodreadsClient::refresh` to reuse a configured client.
+    /// Returns `ScraperError::ScrapeError` if the book has no `goodreads_id`.
+    #[cfg(feature = "net")]
+    pub async fn refresh(&self) -> Result<BookMetadata, ScraperError> {
+        refresh_metadata(&GoodreadsClient::default(), self).await
+    }
+
+    /// Names of the contributors with the given role, in page order.
+    pub fn contributors_with_role(&self, role: &ContributorRole) -> Vec<&str> {
+        self.contributors
+            .iter()
+            .filter(|c| c.parsed_role() == *role)
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    /// Names of the authors in credited order: the primary author first, then co-authors as Goodreads lists them.
+    pub fn authors(&self) -> Vec<&str> {
+        self.contributors_with_role(&ContributorRole::Author)
+    }
+
+    /// Names of the translators of this edition.
+    pub fn translators(&self) -> Vec<&str> {
+        self.contributors_with_role(&ContributorRole::Translator)
+    }
+
+    /// Names of the narrators of this edition, usually only listed for audiobooks.
+    pub fn narrators(&self) -> Vec<&str> {
+        self.contributors_with_role(&ContributorRole::Narrator)
+    }
+
+    /// Flattens every field into a string, keyed by field name, e.g. to write a CSV row.
+    ///
+    /// `None` becomes an empty string, lists and the ratings histogram are joined with `;`, the publication date is in RFC 3339,
+    /// contributors are formatted as `name (role)`, the series as `title #number` and shelves as `name:count`.
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        fn opt<T: ToString>(value: Option<&T>) -> String {
+            value.map(ToString::to_string).unwrap_or_default()
+        }
+
+        let BookMetadata {
+            title,
+            subtitle,
+            description,
+            publisher,
+            publication_date,
+            isbn,
+            contributors,
+            genres,
+            series,
+            page_count,
+            language,
+            image_url,
+            work_id,
+            edition_count,
+            shelves,
+            description_truncated,
+            format,
+            dimensions,
+            weight,
+            similar_books,
+            duration_minutes,
+            publication_year,
+            first_edition_isbn,
+            work_description,
+            edition_popularity,
+            ratings_histogram,
+            goodreads_id,
+            redirected_from,
+            publication_timestamp_ms,
+            language_code,
+        } = self;
+
+        let contributors = contributors
+            .iter()
+            .map(|c| format!("{} ({})", c.name, c.role))
+            .collect::<Vec<_>>()
+            .join(";");
+        let series = series
+            .as_ref()
+            .map(|s| match s.position() {
+                Some(position) => format!("{} #{position}", s.title),
+                None => s.title.clone(),
+            })
+            .unwrap_or_default();
+        let shelves = shelves
+            .iter()
+            .map(|(name, count)| format!("{name}:{count}"))
+            .collect::<Vec<_>>()
+            .join(";");
+        let ratings_histogram = ratings_histogram
+            .map(|counts| counts.map(|count| count.to_string()).join(";"))
+            .unwrap_or_default();
+
+        [
+            ("title", title.clone()),
+            ("subtitle", opt(subtitle.as_ref())),
+            ("description", opt(description.as_ref())),
+            ("publisher", opt(publisher.as_ref())),
+            (
+                "publication_date",
+                opt(publication_date.map(|date| date.to_rfc3339()).as_ref()),
+            ),
+            ("isbn", opt(isbn.as_ref())),
+            ("contributors", contributors),
+            ("genres", genres.join(";")),
+            ("series", series),
+            ("page_count", opt(page_count.as_ref())),
+            ("language", opt(language.as_ref())),
+            ("image_url", opt(image_url.as_ref())),
+            ("work_id", opt(work_id.as_ref())),
+            ("edition_count", opt(edition_count.as_ref())),
+            ("shelves", shelves),
+            ("description_truncated", description_truncated.to_string()),
+            ("format", opt(format.as_ref())),
+            ("dimensions", opt(dimensions.as_ref())),
+            ("weight", opt(weight.as_ref())),
+            ("similar_books", similar_books.join(";")),
+            ("duration_minutes", opt(duration_minutes.as_ref())),
+            ("publication_year", opt(publication_year.as_ref())),
+            ("first_edition_isbn", opt(first_edition_isbn.as_ref())),
+            ("work_description", opt(work_description.as_ref())),
+            ("edition_popularity", opt(edition_popularity.as_ref())),
+            ("ratings_histogram", ratings_histogram),
+            ("goodreads_id", opt(goodreads_id.as_ref())),
+            ("redirected_from", opt(redirected_from.as_ref())),
+            (
+                "publication_timestamp_ms",
+                opt(publication_timestamp_ms.as_ref()),
+            ),
+            ("language_code", opt(language_code.as_ref())),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+}
+
+/// A one-line summary such as "The Last Olympian by Rick Riordan (2009) — Percy Jackson and the Olympians #5",
+/// leaving out the parts that are unknown. The primary contributor is the first author, or the first contributor
+/// when no author is listed.
+impl fmt::Display for BookMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)?;
+
+        let primary = self
+            .contributors
+            .iter()
+            .find(|c| c.parsed_role() == ContributorRole::Author)
+            .or_else(|| self.contributors.first());
+        if let Some(contributor) = primary {
+            write!(f, " by {}", contributor.name)?;
+        }
+        if let Some(year) = self.publication_year {
+            write!(f, " ({year})")?;
+        }
+        if let Some(series) = &self.series {
+            write!(f, " — {}", series.title)?;
+            if let Some(position) = series.position() {
+                write!(f, " #{position}")?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Represents an individual who contributed to the book, such as an author or editor.
-#[derive(Debug, new, PartialEq)]
+#[derive(Debug, Clone, new, PartialEq, Eq, Hash)]
 pub struct BookContributor {
     /// The name of the contributor.
     pub name: String,
@@ -45,32 +421,455 @@ pub struct BookContributor {
     pub role: String,
 }
 
+/// The role a contributor had in the creation of a book, as reported by Goodreads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ContributorRole {
+    Author,
+    Editor,
+    Translator,
+    Illustrator,
+    Narrator,
+    Introduction,
+    Foreword,
+    Afterword,
+    CoverArtist,
+    Contributor,
+    /// Any role not covered by the other variants, holding the role as reported by Goodreads.
+    Other(String),
+}
+
+impl From<&str> for ContributorRole {
+    fn from(role: &str) -> Self {
+        match role.trim().to_lowercase().as_str() {
+            "author" => ContributorRole::Author,
+            "editor" => ContributorRole::Editor,
+            "translator" => ContributorRole::Translator,
+            "illustrator" | "illustrations" => ContributorRole::Illustrator,
+            "narrator" | "reader" => ContributorRole::Narrator,
+            "introduction" => ContributorRole::Introduction,
+            "foreword" => ContributorRole::Foreword,
+            "afterword" => ContributorRole::Afterword,
+            "cover artist" | "cover design" | "cover illustrator" => ContributorRole::CoverArtist,
+            "contributor" => ContributorRole::Contributor,
+            _ => ContributorRole::Other(role.to_string()),
+        }
+    }
+}
+
+impl ContributorRole {
+    // Lower values are listed first.
+    fn priority(&self) -> u8 {
+        match self {
+            ContributorRole::Author => 0,
+            ContributorRole::Editor => 1,
+            ContributorRole::Translator => 2,
+            ContributorRole::Illustrator => 3,
+            _ => 4,
+        }
+    }
+}
+
+impl BookContributor {
+    /// Parses the raw `role` string into a `ContributorRole`.
+    pub fn parsed_role(&self) -> ContributorRole {
+        ContributorRole::from(self.role.as_str())
+    }
+
+    /// The name with consecutive initials joined and whitespace collapsed, so "R. F.  Kuang" and "R.F. Kuang"
+    /// both become "R.F. Kuang". Useful to match authors across data sources; `name` is kept as scraped.
+    pub fn normalized_name(&self) -> String {
+        let mut normalized = String::with_capacity(self.name.len());
+        let mut after_initial = false;
+
+        for word in self.name.split_whitespace() {
+            let initial = INITIALS_RE.is_match(word);
+            if !normalized.is_empty() && (!after_initial || !initial) {
+                normalized.push(' ');
+            }
+            normalized.push_str(word);
+            after_initial = initial;
+        }
+
+        normalized
+    }
+}
+
 /// Represents series information for a book, including the series title and book's position within the series.
-#[derive(Debug, new, PartialEq)]
+///
+/// Equality and hashing compare `number` by its bit pattern, so `-0.0` equals `0.0` and `NaN` equals itself.
+/// This keeps `Eq` and `Hash` consistent, at the cost of differing from plain `f32` comparison for `NaN`.
+///
+/// Series are ordered by `number`, so a reading list can be sorted with `sort()`. Books at the same position
+/// are ordered by series title. A (positive) `NaN` position sorts after every number. The `id`, `label` and
+/// `total` are not part of comparisons or hashing.
+#[derive(Debug, Clone, new)]
 pub struct BookSeries {
     /// The title of the series.
     pub title: String,
     /// The position of the book within the series, represented as a float to accommodate cases like "1.5".
+    /// Ranges such as "1-3" use their first position, spelled out positions such as "Book One" are converted,
+    /// and prequels are 0. Positions without any number, or a missing position, are `NaN`.
     pub number: f32,
+    /// The Goodreads ID of the series, if available. Can be passed to `GoodreadsClient::fetch_series`.
+    #[new(default)]
+    pub id: Option<String>,
+    /// The position as written by Goodreads when it is not a plain number, such as "Prequel", "Book One"
+    /// or "1-3". `None` for plain positions like "2" or "#1.5".
+    #[new(default)]
+    pub label: Option<String>,
+    /// How many primary works the series has, for "Book 5 of 7" displays, when Goodreads embeds the count in
+    /// the book page. Otherwise, `GoodreadsClient::fetch_series` lists the series' entries.
+    #[new(default)]
+    pub total: Option<u32>,
+}
+
+impl BookSeries {
+    /// The position for display: the label when there is one, otherwise the number unless it is `NaN`.
+    pub fn position(&self) -> Option<String> {
+        self.label
+            .clone()
+            .or_else(|| Some(self.number.to_string()).filter(|_| !self.number.is_nan()))
+    }
+
+    fn number_bits(&self) -> u32 {
+        // Adding positive zero turns -0.0 into 0.0 and leaves every other value untouched.
+        (self.number + 0.0).to_bits()
+    }
+}
+
+impl PartialEq for BookSeries {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title && self.number_bits() == other.number_bits()
+    }
+}
+
+impl Eq for BookSeries {}
+
+impl Ord for BookSeries {
+    fn cmp(&self, other: &Self) -> Ordering {
+        f32::from_bits(self.number_bits())
+            .total_cmp(&f32::from_bits(other.number_bits()))
+            .then_with(|| self.title.cmp(&other.title))
+    }
+}
+
+impl PartialOrd for BookSeries {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for BookSeries {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.title.hash(state);
+        self.number_bits().hash(state);
+    }
+}
+
+/// A `BookMetadata` field that can be selected for extraction with `MetadataRequestBuilder::with_fields`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetadataField {
+    Subtitle,
+    Description,
+    Publisher,
+    PublicationDate,
+    Isbn,
+    Contributors,
+    Genres,
+    Series,
+    PageCount,
+    Language,
+    ImageUrl,
+    WorkId,
+    EditionCount,
+    Format,
+    Dimensions,
+    Weight,
+    SimilarBooks,
+    Duration,
+    Popularity,
+    RatingsHistogram,
+}
+
+/// Shelf names Goodreads lists among genres that describe a reader's status or ownership rather than the book.
+/// Used by `MetadataRequestBuilder::with_default_genre_blocklist`.
+pub const DEFAULT_GENRE_BLOCKLIST: &[&str] = &[
+    "to-read",
+    "currently-reading",
+    "read",
+    "favorites",
+    "favourites",
+    "owned",
+    "books-i-own",
+    "owned-books",
+    "wish-list",
+    "wishlist",
+    "default",
+    "library",
+    "kindle",
+    "ebook",
+    "ebooks",
+    "audiobook",
+    "audiobooks",
+    "dnf",
+    "did-not-finish",
+    "abandoned",
+    "re-read",
+];
+
+/// Options controlling which fields are extracted from a book page, and how.
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ExtractOptions {
+    /// Fields to extract. `None` extracts every field. The title is always extracted.
+    pub fields: Option<Vec<MetadataField>>,
+    /// Keeps titles containing a colon whole instead of splitting them into title and subtitle.
+    pub keep_full_title: bool,
+    /// Genres to leave out, compared case-insensitively with spaces treated as dashes. Empty keeps every genre.
+    pub genre_blocklist: Vec<String>,
+    /// Keeps the description's whitespace as published instead of collapsing runs of whitespace into one space.
+    pub preserve_whitespace: bool,
+    /// Whether to fetch the work's user shelves, which costs an additional request.
+    #[cfg(feature = "net")]
+    pub shelves: bool,
+    /// Language name (e.g. "French") of the edition to scrape. When the requested edition is in another
+    /// language, the work's editions are listed and the first one in this language is scraped instead,
+    /// falling back to the requested edition when none matches. Costs up to two additional requests.
+    #[cfg(feature = "net")]
+    pub language: Option<String>,
+    /// Whether to look up the ISBN of the work's first edition, which costs an additional request.
+    #[cfg(feature = "net")]
+    pub first_edition: bool,
+}
+
+impl ExtractOptions {
+    pub(crate) fn includes(&self, field: MetadataField) -> bool {
+        self.fields.as_ref().is_none_or(|f| f.contains(&field))
+    }
+
+    fn extract<T: Default>(&self, field: MetadataField, extractor: impl FnOnce() -> T) -> T {
+        if self.includes(field) {
+            extractor()
+        } else {
+            T::default()
+        }
+    }
+}
+
+#[cfg(feature = "net")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(client, options), err(Debug))
+)]
+pub async fn fetch_metadata(
+    client: &GoodreadsClient,
+    goodreads_id: &str,
+    options: &ExtractOptions,
+) -> Result<BookMetadata, ScraperError> {
+    Ok(fetch_metadata_and_work_id(client, goodreads_id, options)
+        .await?
+        .0)
+}
+
+// Also returns the book's work ID, read from the page data even when `options` leaves it out of the metadata.
+#[cfg(feature = "net")]
+pub(crate) async fn fetch_metadata_and_work_id(
+    client: &GoodreadsClient,
+    goodreads_id: &str,
+    options: &ExtractOptions,
+) -> Result<(BookMetadata, Option<String>), ScraperError> {
+    let requested_id = goodreads_id;
+    let (mut metadata, mut goodreads_id) = fetch_book_page(client, requested_id).await?;
+    let redirected_from = Some(requested_id.to_string()).filter(|id| *id != goodreads_id);
+
+    if let Some(language) = &options.language
+        && let Some(edition_id) =
+            find_language_edition(client, &metadata, &goodreads_id, language).await?
+    {
+        (metadata, goodreads_id) = fetch_book_page(client, &edition_id).await?;
+    }
+
+    let mut book = parse_metadata(&metadata, &goodreads_id, options)?;
+    book.redirected_from = redirected_from;
+
+    let work_id = extract_page_work_id(&metadata, &goodreads_id)?;
+    if let Some(work_id) = &work_id {
+        if options.shelves {
+            book.shelves = fetch_shelves(client, work_id).await?;
+        }
+        if options.first_edition {
+            let editions = fetch_editions(client, work_id).await?;
+            book.first_edition_isbn = first_edition(&editions).and_then(|e| e.isbn.clone());
+        }
+    }
+
+    Ok((book, work_id))
+}
+
+// The earliest dated edition with an ISBN. Editions without a year are only used when none is dated.
+#[cfg(feature = "net")]
+fn first_edition(editions: &[BookEdition]) -> Option<&BookEdition> {
+    let with_isbn = || editions.iter().filter(|edition| edition.isbn.is_some());
+    with_isbn()
+        .filter(|edition| edition.publication_year.is_some())
+        .min_by_key(|edition| edition.publication_year)
+        .or_else(|| with_isbn().next())
+}
+
+#[cfg(feature = "net")]
+pub async fn fetch_page_data(
+    client: &GoodreadsClient,
+    goodreads_id: &str,
+) -> Result<Value, ScraperError> {
+    Ok(fetch_book_page(client, goodreads_id).await?.0)
+}
+
+// Also returns the ID of the page Goodreads redirected to, which keys the book in the page data.
+#[cfg(feature = "net")]
+async fn fetch_book_page(
+    client: &GoodreadsClient,
+    goodreads_id: &str,
+) -> Result<(Value, String), ScraperError> {
+    let url = format!("https://www.goodreads.com/book/show/{goodreads_id}");
+    let mut retries = 0;
+    let (response, metadata) = loop {
+        let response = client.get(&url).await?;
+        match extract_book_metadata(&response.text()) {
+            // Truncated responses end the page data early, and usually come through whole when fetched again.
+            Err(ScraperError::SerializeError(error))
+                if error.is_eof() && retries < TRUNCATED_PAGE_RETRIES =>
+            {
+                warn!("Page data of {goodreads_id} was truncated, fetching it again");
+                retries += 1;
+                client.pace().await;
+            }
+            result => break (response, result?),
+        }
+    };
+
+    let canonical_id = match response.book_id() {
+        Some(id) if id.trim_start_matches('0') != goodreads_id.trim_start_matches('0') => {
+            warn!("Goodreads ID {goodreads_id} redirected to {id}");
+            id
+        }
+        _ => goodreads_id.to_string(),
+    };
+
+    Ok((metadata, canonical_id))
+}
+
+// Returns the ID of an edition in `language`, or `None` when the page already is in that language or no edition is.
+#[cfg(feature = "net")]
+async fn find_language_edition(
+    client: &GoodreadsClient,
+    metadata: &Value,
+    goodreads_id: &str,
+    language: &str,
+) -> Result<Option<String>, ScraperError> {
+    let amazon_id = extract_amazon_id(metadata, goodreads_id)?;
+    let is_language = |name: Option<&str>| {
+        name.is_some_and(|name| name.trim().eq_ignore_ascii_case(language.trim()))
+    };
+
+    if is_language(extract_language(metadata, &amazon_id).as_deref()) {
+        return Ok(None);
+    }
+
+    let Some(work_id) = extract_work_id(metadata, &amazon_id) else {
+        warn!("No work found to look up {language} editions");
+        return Ok(None);
+    };
+
+    let edition = fetch_editions(client, &work_id)
+        .await?
+        .into_iter()
+        .find(|edition| is_language(edition.language.as_deref()));
+    if edition.is_none() {
+        warn!("No {language} edition found, using the requested edition");
+    }
+
+    Ok(edition.map(|edition| edition.goodreads_id))
 }
 
-pub async fn fetch_metadata(goodreads_id: &str) -> Result<BookMetadata, ScraperError> {
-    let metadata = extract_book_metadata(goodreads_id).await?;
-    let amazon_id = extract_amazon_id(&metadata, goodreads_id)?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(metadata, options), err(Debug))
+)]
+fn parse_metadata(
+    metadata: &Value,
+    goodreads_id: &str,
+    options: &ExtractOptions,
+) -> Result<BookMetadata, ScraperError> {
+    let amazon_id = extract_amazon_id(metadata, goodreads_id)?;
 
-    let (title, subtitle) = extract_title_and_subtitle(&metadata, &amazon_id)?;
-    let description = extract_description(&metadata, &amazon_id);
-    let image_url = extract_image_url(&metadata, &amazon_id);
-    let contributors = extract_contributors(&metadata, &amazon_id);
-    let genres = extract_genres(&metadata, &amazon_id);
-    let publisher = extract_publisher(&metadata, &amazon_id);
-    let publication_date = extract_publication_date(&metadata, &amazon_id);
-    let isbn = extract_isbn(&metadata, &amazon_id);
-    let page_count = extract_page_count(&metadata, &amazon_id);
-    let language = extract_language(&metadata, &amazon_id);
-    let series = extract_series(&metadata, &amazon_id);
+    let (title, subtitle) =
+        extract_title_and_subtitle(metadata, goodreads_id, &amazon_id, options)?;
+    let subtitle = options.extract(MetadataField::Subtitle, || subtitle);
+    let work_description = options.extract(MetadataField::Description, || {
+        extract_work_description(metadata, &amazon_id, options.preserve_whitespace)
+    });
+    let description = options
+        .extract(MetadataField::Description, || {
+            extract_description(metadata, &amazon_id, options.preserve_whitespace)
+        })
+        .or_else(|| work_description.clone());
+    let description_truncated = description.as_deref().is_some_and(is_truncated);
+    let image_url = options.extract(MetadataField::ImageUrl, || {
+        extract_image_url(metadata, &amazon_id)
+    });
+    let contributors = options.extract(MetadataField::Contributors, || {
+        extract_contributors(metadata, &amazon_id)
+    });
+    let genres = options.extract(MetadataField::Genres, || {
+        extract_genres(metadata, &amazon_id, &options.genre_blocklist)
+    });
+    let publisher = options.extract(MetadataField::Publisher, || {
+        extract_publisher(metadata, &amazon_id)
+    });
+    let (publication_date, publication_year, publication_timestamp_ms) = options
+        .extract(MetadataField::PublicationDate, || {
+            extract_publication(metadata, &amazon_id)
+        });
+    let isbn = options.extract(MetadataField::Isbn, || extract_isbn(metadata, &amazon_id));
+    let page_count = options.extract(MetadataField::PageCount, || {
+        extract_page_count(metadata, &amazon_id)
+    });
+    let duration_minutes = options.extract(MetadataField::Duration, || {
+        extract_duration_minutes(metadata, &amazon_id)
+    });
+    let language = options.extract(MetadataField::Language, || {
+        extract_language(metadata, &amazon_id)
+    });
+    let language_code = language.as_deref().and_then(language_code);
+    let series = options.extract(MetadataField::Series, || {
+        extract_series(metadata, &amazon_id)
+    });
+    let work_id = options.extract(MetadataField::WorkId, || {
+        extract_work_id(metadata, &amazon_id)
+    });
+    let edition_count = options.extract(MetadataField::EditionCount, || {
+        extract_edition_count(metadata, &amazon_id)
+    });
+    let format = options.extract(MetadataField::Format, || {
+        extract_detail(metadata, &amazon_id, "format")
+    });
+    let dimensions = options.extract(MetadataField::Dimensions, || {
+        extract_detail(metadata, &amazon_id, "dimensions")
+    });
+    let weight = options.extract(MetadataField::Weight, || {
+        extract_detail(metadata, &amazon_id, "weight")
+    });
+    let similar_books = options.extract(MetadataField::SimilarBooks, || {
+        extract_similar_books(metadata)
+    });
+    let edition_popularity = options.extract(MetadataField::Popularity, || {
+        extract_popularity(metadata, &amazon_id)
+    });
+    let ratings_histogram = options.extract(MetadataField::RatingsHistogram, || {
+        extract_ratings_histogram(metadata, &amazon_id)
+    });
 
-    let metadata = BookMetadata::new(
+    Ok(BookMetadata {
         title,
         subtitle,
         description,
@@ -83,19 +882,36 @@ pub async fn fetch_metadata(goodreads_id: &str) -> Result<BookMetadata, ScraperE
         page_count,
         language,
         image_url,
-    );
-
-    Ok(metadata)
+        work_id,
+        edition_count,
+        shelves: Vec::new(),
+        description_truncated,
+        format,
+        dimensions,
+        weight,
+        similar_books,
+        duration_minutes,
+        publication_year,
+        first_edition_isbn: None,
+        work_description,
+        edition_popularity,
+        ratings_histogram,
+        goodreads_id: Some(extract_canonical_id(metadata, &amazon_id, goodreads_id)),
+        redirected_from: None,
+        publication_timestamp_ms,
+        language_code,
+    })
 }
 
-async fn extract_book_metadata(goodreads_id: &str) -> Result<Value, ScraperError> {
-    let url = format!("https://www.goodreads.com/book/show/{goodreads_id}");
-    let document = Html::parse_document(&get(&url).await?.text().await?);
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(html_len = html.len()), err(Debug)))]
+fn extract_book_metadata(html: &str) -> Result<Value, ScraperError> {
+    let document = Html::parse_document(html);
     let metadata_selector = Selector::parse(r#"script[id="__NEXT_DATA__"]"#)?;
     let metadata = &document.select(&metadata_selector).next();
 
     let metadata = match metadata {
         None => {
+            check_challenge(html)?;
             error!("Failed to scrape book metadata");
             return Err(ScraperError::ScrapeError(
                 "Failed to scrape book metadata".to_string(),
@@ -107,41 +923,274 @@ async fn extract_book_metadata(goodreads_id: &str) -> Result<Value, ScraperError
     Ok(metadata)
 }
 
+#[cfg(feature = "net")]
+pub async fn fetch_quotes(
+    client: &GoodreadsClient,
+    work_id: &str,
+) -> Result<Vec<String>, ScraperError> {
+    let url = format!("https://www.goodreads.com/work/quotes/{}", encode(work_id));
+    let document = Html::parse_document(&client.get(&url).await?.text());
+    parse_quotes(&document)
+}
+
+#[cfg(feature = "net")]
+pub async fn fetch_cover(
+    client: &GoodreadsClient,
+    metadata: &BookMetadata,
+) -> Result<Vec<u8>, ScraperError> {
+    let Some(image_url) = &metadata.image_url else {
+        error!("Book has no cover image");
+        return Err(ScraperError::ScrapeError(
+            "Book has no cover image".to_string(),
+        ));
+    };
+
+    let url = full_resolution_cover_url(image_url);
+    Ok(client.get(&url).await?.body)
+}
+
+#[cfg(feature = "net")]
+pub async fn refresh_metadata(
+    client: &GoodreadsClient,
+    metadata: &BookMetadata,
+) -> Result<BookMetadata, ScraperError> {
+    let Some(goodreads_id) = &metadata.goodreads_id else {
+        error!("Book has no Goodreads ID");
+        return Err(ScraperError::ScrapeError(
+            "Book has no Goodreads ID".to_string(),
+        ));
+    };
+
+    fetch_metadata(client, goodreads_id, &ExtractOptions::default()).await
+}
+
+// Amazon-hosted covers may carry a size modifier (e.g. "._SY475_.jpg"). Dropping it serves the original image.
+#[cfg(feature = "net")]
+fn full_resolution_cover_url(url: &str) -> String {
+    COVER_SIZE_RE.replace(url, "$1").to_string()
+}
+
+#[cfg(feature = "net")]
+pub async fn fetch_shelves(
+    client: &GoodreadsClient,
+    work_id: &str,
+) -> Result<Vec<(String, i64)>, ScraperError> {
+    let url = format!("https://www.goodreads.com/work/shelves/{}", encode(work_id));
+    let document = Html::parse_document(&client.get(&url).await?.text());
+    parse_shelves(&document)
+}
+
+#[cfg(feature = "net")]
+fn parse_shelves(document: &Html) -> Result<Vec<(String, i64)>, ScraperError> {
+    let shelf_selector = Selector::parse("div.shelfStat")?;
+    let name_selector = Selector::parse("a.actionLinkLite")?;
+    let count_selector = Selector::parse("div.smallText")?;
+
+    let shelves = document
+        .select(&shelf_selector)
+        .filter_map(|shelf| {
+            let name = shelf
+                .select(&name_selector)
+                .next()?
+                .text()
+                .collect::<String>();
+            let count = shelf
+                .select(&count_selector)
+                .next()?
+                .text()
+                .collect::<String>();
+            let count = count
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect::<String>()
+                .parse::<i64>();
+
+            let (Some(name), Ok(count)) = (to_string(&Value::from(name)), count) else {
+                warn!("Failed to parse shelf");
+                return None;
+            };
+
+            Some((name, count))
+        })
+        .collect();
+
+    Ok(shelves)
+}
+
+#[cfg(feature = "net")]
+pub async fn fetch_series(
+    client: &GoodreadsClient,
+    series_id: &str,
+) -> Result<Vec<(f32, String)>, ScraperError> {
+    let url = format!("https://www.goodreads.com/series/{}", encode(series_id));
+    let document = Html::parse_document(&client.get(&url).await?.text());
+    parse_series(&document)
+}
+
+#[cfg(feature = "net")]
+fn parse_series(document: &Html) -> Result<Vec<(f32, String)>, ScraperError> {
+    let entry_selector = Selector::parse("div.listWithDividers__item")?;
+    let position_selector = Selector::parse("h3")?;
+    let link_selector = Selector::parse(r#"a[href*="/book/show/"]"#)?;
+
+    let mut entries: Vec<(f32, String)> = document
+        .select(&entry_selector)
+        .filter_map(|entry| {
+            // Omnibus entries ("Book 1-3") have no single position and are skipped.
+            let position = entry
+                .select(&position_selector)
+                .next()?
+                .text()
+                .collect::<String>();
+            let position = position.trim().strip_prefix("Book ")?.parse::<f32>().ok()?;
+            let link = entry.select(&link_selector).next()?.value().attr("href")?;
+            let (_, path) = link.split_once("/book/show/")?;
+            let id = path
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            Some((position, id)).filter(|(_, id)| !id.is_empty())
+        })
+        .collect();
+    entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Ok(entries)
+}
+
+#[cfg(feature = "net")]
+fn parse_quotes(document: &Html) -> Result<Vec<String>, ScraperError> {
+    let quote_selector = Selector::parse("div.quoteText")?;
+
+    let quotes = document
+        .select(&quote_selector)
+        .filter_map(|quote| {
+            let text = quote.text().collect::<String>();
+            let text = text.split('―').next().unwrap_or_default();
+            let text = text.trim().trim_matches(['“', '”', '"']);
+            to_string(&Value::from(text))
+        })
+        .collect();
+
+    Ok(quotes)
+}
+
 fn extract_amazon_id(metadata: &Value, goodreads_id: &str) -> Result<String, ScraperError> {
     let amazon_id_key = format!("getBookByLegacyId({{\"legacyId\":\"{goodreads_id}\"}})");
-    let amazon_id =
-        &metadata["props"]["pageProps"]["apolloState"]["ROOT_QUERY"][amazon_id_key]["__ref"];
-    let Some(amazon_id) = to_string(amazon_id) else {
+    let root = &metadata["props"]["pageProps"]["apolloState"]["ROOT_QUERY"];
+    let amazon_id = to_string(&root[&amazon_id_key]["__ref"])
+        .or_else(|| find_legacy_id_ref(root, goodreads_id));
+    let Some(amazon_id) = amazon_id else {
+        // Deleted books still serve page data, but their book query resolves to nothing.
+        if root.get(&amazon_id_key).is_some_and(Value::is_null) {
+            warn!("Book {goodreads_id} is not available");
+            return Err(ScraperError::Unavailable);
+        }
         error!("Failed to scrape Amazon ID");
-        return Err(ScraperError::ScrapeError(
-            "Failed to scrape Amazon ID".to_string(),
-        ));
+        return Err(ScraperError::MissingField {
+            path: format!("props.pageProps.apolloState.ROOT_QUERY.{amazon_id_key}.__ref"),
+            goodreads_id: goodreads_id.to_string(),
+        });
     };
 
     Ok(amazon_id)
 }
 
+// Goodreads has changed the shape of the query arguments before, so any `getBookByLegacyId` query
+// for the same ID is accepted when the exact key is missing.
+fn find_legacy_id_ref(root: &Value, goodreads_id: &str) -> Option<String> {
+    let id = goodreads_id.trim_start_matches('0');
+    let (key, query) = root.as_object()?.iter().find(|(key, _)| {
+        LEGACY_ID_KEY_RE
+            .captures(key)
+            .is_some_and(|captures| captures[1].trim_start_matches('0') == id)
+    })?;
+
+    warn!("Book query key changed format, found {key}");
+    to_string(&query["__ref"])
+}
+
+// A missing book node, or one carrying nothing but identifiers, as served for removed books.
+fn is_placeholder(book: &Value) -> bool {
+    book.as_object().is_none_or(|fields| {
+        fields
+            .keys()
+            .all(|key| matches!(key.as_str(), "__typename" | "id" | "legacyId"))
+    })
+}
+
 fn extract_title_and_subtitle(
     metadata: &Value,
+    goodreads_id: &str,
     amazon_id: &str,
+    options: &ExtractOptions,
 ) -> Result<(String, Option<String>), ScraperError> {
-    let title = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["title"];
-    let Some(title) = to_string(title) else {
+    let book = &metadata["props"]["pageProps"]["apolloState"][amazon_id];
+    let Some(title) = to_string(&book["title"]) else {
+        if is_placeholder(book) {
+            warn!("Book {goodreads_id} is not available");
+            return Err(ScraperError::Unavailable);
+        }
         error!("Failed to scrape book title");
-        return Err(ScraperError::ScrapeError(
-            "Failed to scrape book title".to_string(),
-        ));
+        return Err(ScraperError::MissingField {
+            path: format!("props.pageProps.apolloState.{amazon_id}.title"),
+            goodreads_id: goodreads_id.to_string(),
+        });
     };
 
+    // A dedicated subtitle field is preferred, splitting on the first colon is only a heuristic.
+    let subtitle = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["subtitle"];
+    if let Some(subtitle) = to_string(subtitle) {
+        let title = title
+            .strip_suffix(subtitle.as_str())
+            .map_or(title.as_str(), |t| t.trim_end().trim_end_matches(':'))
+            .trim_end()
+            .to_string();
+        return Ok((title, Some(subtitle)));
+    }
+
+    if options.keep_full_title {
+        return Ok((title, None));
+    }
+
     match title.split_once(':') {
         Some((title, subtitle)) => Ok((title.to_string(), Some(subtitle.trim().to_string()))),
         None => Ok((title.clone(), None)),
     }
 }
 
-fn extract_description(metadata: &Value, amazon_id: &str) -> Option<String> {
+fn extract_description(
+    metadata: &Value,
+    amazon_id: &str,
+    preserve_whitespace: bool,
+) -> Option<String> {
     let description = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["description"];
-    to_string(description)
+    if !preserve_whitespace {
+        return to_string(description);
+    }
+
+    description
+        .as_str()
+        .map(|s| decode_html_entities(s).trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn extract_work_description(
+    metadata: &Value,
+    amazon_id: &str,
+    preserve_whitespace: bool,
+) -> Option<String> {
+    let key =
+        to_string(&metadata["props"]["pageProps"]["apolloState"][amazon_id]["work"]["__ref"])?;
+    extract_description(metadata, &key, preserve_whitespace)
+}
+
+fn is_truncated(description: &str) -> bool {
+    let description = description.trim_end();
+    let description = description
+        .strip_suffix("</p>")
+        .unwrap_or(description)
+        .trim_end();
+    description.ends_with('…') || description.ends_with("...")
 }
 
 fn extract_image_url(metadata: &Value, amazon_id: &str) -> Option<String> {
@@ -150,52 +1199,34 @@ fn extract_image_url(metadata: &Value, amazon_id: &str) -> Option<String> {
 }
 
 fn extract_contributors(metadata: &Value, amazon_id: &str) -> Vec<BookContributor> {
-    let mut contributors = Vec::new();
-
-    let primary =
-        metadata["props"]["pageProps"]["apolloState"][amazon_id]["primaryContributorEdge"]
-            .as_object()
-            .map(|obj| (to_string(&obj["role"]), to_string(&obj["node"]["__ref"])));
-
-    match primary {
-        Some((Some(role), Some(reference))) => {
-            if let Some(contributor) = fetch_contributor(metadata, (role, reference)) {
-                contributors.push(contributor);
-            }
-        }
-        Some(_) => {
-            warn!("Failed to parse contributor");
-        }
-        None => (),
-    }
+    let book = &metadata["props"]["pageProps"]["apolloState"][amazon_id];
 
-    let Some(secondary) =
-        metadata["props"]["pageProps"]["apolloState"][amazon_id]["secondaryContributorEdges"]
-            .as_array()
-    else {
-        return contributors
-            .into_iter()
-            .filter(|s| !s.name.to_lowercase().eq("unknown author"))
-            .collect();
-    };
+    // Anthologies often have a null primary contributor, with every editor and author listed as a secondary one.
+    let primary = Some(&book["primaryContributorEdge"]).filter(|edge| !edge.is_null());
+    let secondary = book["secondaryContributorEdges"]
+        .as_array()
+        .into_iter()
+        .flatten();
 
-    for contributor in secondary {
-        let role = to_string(&contributor["role"]);
-        let key = to_string(&contributor["node"]["__ref"]);
-        if role.is_none() || key.is_none() {
-            warn!("Failed to parse contributor");
-            continue;
-        }
+    let mut contributors: Vec<BookContributor> = primary
+        .into_iter()
+        .chain(secondary)
+        .filter_map(|edge| {
+            let (Some(role), Some(key)) =
+                (to_string(&edge["role"]), to_string(&edge["node"]["__ref"]))
+            else {
+                warn!("Failed to parse contributor");
+                return None;
+            };
 
-        if let Some(contributor) = fetch_contributor(metadata, (role.unwrap(), key.unwrap())) {
-            contributors.push(contributor);
-        }
-    }
+            fetch_contributor(metadata, (role, key))
+        })
+        .filter(|s| !s.name.to_lowercase().eq("unknown author"))
+        .collect();
 
+    // The sort is stable, so the primary contributor stays ahead of others with the same role.
+    contributors.sort_by_key(|c| c.parsed_role().priority());
     contributors
-        .into_iter()
-        .filter(|s| !s.name.to_lowercase().eq("unknown author"))
-        .collect()
 }
 
 fn fetch_contributor(metadata: &Value, (role, key): (String, String)) -> Option<BookContributor> {
@@ -208,22 +1239,37 @@ fn fetch_contributor(metadata: &Value, (role, key): (String, String)) -> Option<
     name.map(|n| BookContributor::new(n, role))
 }
 
-fn extract_genres(metadata: &Value, amazon_id: &str) -> Vec<String> {
+fn extract_genres(metadata: &Value, amazon_id: &str, blocklist: &[String]) -> Vec<String> {
     let genres = metadata["props"]["pageProps"]["apolloState"][amazon_id]["bookGenres"].as_array();
 
     let Some(genres) = genres else {
         return vec![];
     };
 
-    genres
-        .iter()
-        .filter_map(|genre| {
-            to_string(&genre["genre"]["name"]).or_else(|| {
-                warn!("Failed to parse genre name");
-                None
-            })
-        })
-        .collect()
+    let mut names: Vec<String> = Vec::with_capacity(genres.len());
+    for genre in genres {
+        let Some(name) = to_string(&genre["genre"]["name"]) else {
+            warn!("Failed to parse genre name");
+            continue;
+        };
+
+        if is_blocked_genre(&name, blocklist) {
+            continue;
+        }
+
+        // Keeps the first (highest ranked) occurrence of a duplicated genre.
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+fn is_blocked_genre(name: &str, blocklist: &[String]) -> bool {
+    let normalize = |s: &str| s.trim().to_lowercase().replace(' ', "-");
+    let name = normalize(name);
+    blocklist.iter().any(|blocked| normalize(blocked) == name)
 }
 
 fn extract_publisher(metadata: &Value, amazon_id: &str) -> Option<String> {
@@ -232,38 +1278,111 @@ fn extract_publisher(metadata: &Value, amazon_id: &str) -> Option<String> {
     to_string(publisher)
 }
 
-fn extract_publication_date(metadata: &Value, amazon_id: &str) -> Option<DateTime<Utc>> {
-    match &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["publicationTime"] {
-        Value::Null => None,
-        Value::Number(number) => {
-            let timestamp = number.as_i64().map(DateTime::from_timestamp_millis);
+// The publication date, its year (falling back to the work's original publication year) and the raw timestamp.
+fn extract_publication(
+    metadata: &Value,
+    amazon_id: &str,
+) -> (Option<DateTime<Utc>>, Option<i32>, Option<i64>) {
+    let date = extract_publication_date(metadata, amazon_id);
+    let year = date
+        .map(|date| date.year())
+        .or_else(|| extract_original_publication_year(metadata, amazon_id));
 
-            if timestamp.is_none() {
-                warn!("Failed to parse publication date");
-            }
+    (
+        date,
+        year,
+        extract_publication_timestamp(metadata, amazon_id),
+    )
+}
 
-            timestamp.flatten()
-        }
-        _ => panic!("Publication date must be a timestamp"),
+fn extract_publication_date(metadata: &Value, amazon_id: &str) -> Option<DateTime<Utc>> {
+    let date = match &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["publicationTime"]
+    {
+        Value::Null => return None,
+        Value::Number(number) => number.as_i64().and_then(DateTime::from_timestamp_millis),
+        Value::String(date) => parse_date(date),
+        _ => None,
+    };
+
+    if date.is_none() {
+        warn!("Failed to parse publication date");
     }
+
+    date
 }
 
-fn extract_isbn(metadata: &Value, amazon_id: &str) -> Option<String> {
-    let isbn = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["isbn"];
-    if let Some(i) = to_string(isbn) {
-        return Some(i);
-    }
+// Only numeric millisecond timestamps are kept raw. Strings such as "1847" are dates, not timestamps.
+fn extract_publication_timestamp(metadata: &Value, amazon_id: &str) -> Option<i64> {
+    metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["publicationTime"].as_i64()
+}
 
-    let isbn13 = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["isbn13"];
-    if let Some(i) = to_string(isbn13) {
-        return Some(i);
-    }
+fn extract_original_publication_year(metadata: &Value, amazon_id: &str) -> Option<i32> {
+    let key =
+        to_string(&metadata["props"]["pageProps"]["apolloState"][amazon_id]["work"]["__ref"])?;
+    let date = match &metadata["props"]["pageProps"]["apolloState"][key]["details"]["publicationTime"]
+    {
+        Value::Number(number) => number.as_i64().and_then(DateTime::from_timestamp_millis),
+        Value::String(date) => parse_date(date),
+        _ => None,
+    };
 
-    let asin = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["asin"];
-    to_string(asin)
+    date.map(|date| date.year())
 }
 
-fn extract_page_count(metadata: &Value, amazon_id: &str) -> Option<i64> {
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+
+    if let Some(year) = parse_year(date) {
+        return year.and_hms_opt(0, 0, 0).map(|date| date.and_utc());
+    }
+
+    if let Ok(millis) = date.parse::<i64>() {
+        return DateTime::from_timestamp_millis(millis);
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.to_utc());
+    }
+
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+// Year-only dates map to January 1st. Years use astronomical numbering, so "1 BC" is year 0.
+fn parse_year(date: &str) -> Option<NaiveDate> {
+    let captures = YEAR_RE.captures(date)?;
+    let year = captures[1].parse::<i32>().ok()?;
+    let year = if captures.get(2).is_some() {
+        1 - year
+    } else {
+        year
+    };
+    NaiveDate::from_ymd_opt(year, 1, 1)
+}
+
+fn extract_isbn(metadata: &Value, amazon_id: &str) -> Option<String> {
+    let isbn = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["isbn"];
+    if let Some(i) = to_string(isbn) {
+        return Some(i);
+    }
+
+    let isbn13 = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["isbn13"];
+    if let Some(i) = to_string(isbn13) {
+        return Some(i);
+    }
+
+    let asin = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["asin"];
+    to_string(asin)
+}
+
+fn extract_page_count(metadata: &Value, amazon_id: &str) -> Option<i64> {
+    if is_audiobook(metadata, amazon_id) {
+        return None;
+    }
+
     let count =
         metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["numPages"].as_i64();
     match count {
@@ -272,55 +1391,1189 @@ fn extract_page_count(metadata: &Value, amazon_id: &str) -> Option<i64> {
     }
 }
 
+// Audiobook durations are listed either as seconds or as text such as "11 hours, 52 minutes".
+fn extract_duration_minutes(metadata: &Value, amazon_id: &str) -> Option<i64> {
+    if !is_audiobook(metadata, amazon_id) {
+        return None;
+    }
+
+    let duration = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["duration"];
+    if let Some(seconds) = duration.as_i64() {
+        return Some(seconds / 60).filter(|minutes| *minutes > 0);
+    }
+
+    let duration = to_string(duration)?;
+    let captures = DURATION_RE.captures(&duration)?;
+    let hours = captures.get(1).and_then(|h| h.as_str().parse::<i64>().ok());
+    let minutes = captures.get(2).and_then(|m| m.as_str().parse::<i64>().ok());
+    if hours.is_none() && minutes.is_none() {
+        warn!("Failed to parse audiobook duration");
+        return None;
+    }
+
+    Some(hours.unwrap_or(0) * 60 + minutes.unwrap_or(0))
+}
+
+fn is_audiobook(metadata: &Value, amazon_id: &str) -> bool {
+    extract_detail(metadata, amazon_id, "format").is_some_and(|format| {
+        let format = format.to_lowercase();
+        format.contains("audio") || format.contains("mp3")
+    })
+}
+
 fn extract_language(metadata: &Value, amazon_id: &str) -> Option<String> {
     let language =
         &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"]["language"]["name"];
     to_string(language)
 }
 
+fn language_code(language: &str) -> Option<String> {
+    LANGUAGE_CODES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(language.trim()))
+        .map(|(_, code)| (*code).to_string())
+}
+
 fn extract_series(metadata: &Value, amazon_id: &str) -> Option<BookSeries> {
     let series_array =
         metadata["props"]["pageProps"]["apolloState"][amazon_id]["bookSeries"].as_array()?;
 
     let series = series_array.first()?;
 
-    let Some(position) = series["userPosition"]
-        .as_str()
-        .map(|s| s.split('-').next().unwrap_or(""))
-        .and_then(|s| s.parse::<f32>().ok())
-    else {
+    let position = to_string(&series["userPosition"]);
+    let number = position.as_deref().map_or(f32::NAN, parse_series_position);
+    if number.is_nan() {
         warn!("Failed to parse series number");
-        return None;
-    };
+    }
 
     let Some(key) = to_string(&series["series"]["__ref"]) else {
         warn!("Failed to parse series key");
         return None;
     };
 
-    let title = &metadata["props"]["pageProps"]["apolloState"][key]["title"];
+    let title = &metadata["props"]["pageProps"]["apolloState"][&key]["title"];
     let Some(title) = to_string(title) else {
         warn!("Failed to parse series title");
         return None;
     };
 
-    Some(BookSeries::new(title, position))
+    let mut series = BookSeries::new(title, number);
+    series.total = metadata["props"]["pageProps"]["apolloState"][&key]["primaryWorksCount"]
+        .as_u64()
+        .and_then(|count| u32::try_from(count).ok());
+    series.label = position.filter(|position| {
+        position
+            .trim_start_matches('#')
+            .trim()
+            .parse::<f32>()
+            .is_err()
+    });
+    series.id =
+        to_string(&metadata["props"]["pageProps"]["apolloState"][&key]["webUrl"]).and_then(|url| {
+            let (_, path) = url.split_once("/series/")?;
+            let id = path
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>();
+            Some(id).filter(|id| !id.is_empty())
+        });
+
+    Some(series)
+}
+
+// Parses series positions such as "5", "#1.5", "1-3", "Book One", "Part II" or "Prequel" into a sortable number.
+fn parse_series_position(position: &str) -> f32 {
+    const NUMBERS: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+        "twelve",
+    ];
+    const ORDINALS: [&str; 12] = [
+        "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+        "tenth", "eleventh", "twelfth",
+    ];
+    const NUMERALS: [&str; 12] = [
+        "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii",
+    ];
+
+    let position = position.trim().trim_start_matches('#').to_lowercase();
+    let position = ["book ", "volume ", "vol. ", "vol ", "part ", "no. "]
+        .iter()
+        .find_map(|prefix| position.strip_prefix(prefix))
+        .unwrap_or(&position)
+        .trim_start_matches('#');
+
+    // Omnibus ranges such as "1-3" are placed at their first entry.
+    let first = position.split(['-', '–']).next().unwrap_or("").trim();
+    if let Ok(number) = first.parse::<f32>() {
+        return number;
+    }
+    if first == "prequel" {
+        return 0.0;
+    }
+
+    [NUMBERS, ORDINALS, NUMERALS]
+        .iter()
+        .find_map(|words| words.iter().zip(1u8..).find(|(word, _)| **word == first))
+        .map_or(f32::NAN, |(_, number)| f32::from(number))
+}
+
+fn extract_detail(metadata: &Value, amazon_id: &str, key: &str) -> Option<String> {
+    let detail = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["details"][key];
+    to_string(detail)
+}
+
+fn extract_similar_books(metadata: &Value) -> Vec<String> {
+    let Some(root) = metadata["props"]["pageProps"]["apolloState"]["ROOT_QUERY"].as_object() else {
+        return vec![];
+    };
+
+    let edges = root
+        .iter()
+        .filter(|(key, _)| key.starts_with("getSimilarBooks"))
+        .filter_map(|(_, similar)| similar["edges"].as_array())
+        .flatten();
+
+    let mut ids: Vec<String> = Vec::new();
+    for edge in edges {
+        let Some(key) = to_string(&edge["node"]["__ref"]) else {
+            warn!("Failed to parse similar book key");
+            continue;
+        };
+
+        let id = &metadata["props"]["pageProps"]["apolloState"][key]["legacyId"];
+        let Some(id) = id.as_i64().map(|i| i.to_string()).or_else(|| to_string(id)) else {
+            warn!("Failed to parse similar book ID");
+            continue;
+        };
+
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    ids
+}
+
+// The work ID of the book `goodreads_id` in already fetched page data.
+#[cfg(feature = "net")]
+pub(crate) fn extract_page_work_id(
+    metadata: &Value,
+    goodreads_id: &str,
+) -> Result<Option<String>, ScraperError> {
+    let amazon_id = extract_amazon_id(metadata, goodreads_id)?;
+    Ok(extract_work_id(metadata, &amazon_id))
+}
+
+fn extract_work_id(metadata: &Value, amazon_id: &str) -> Option<String> {
+    let key =
+        to_string(&metadata["props"]["pageProps"]["apolloState"][amazon_id]["work"]["__ref"])?;
+    let id = &metadata["props"]["pageProps"]["apolloState"][key]["legacyId"];
+    id.as_i64().map(|i| i.to_string()).or_else(|| to_string(id))
+}
+
+fn extract_edition_count(metadata: &Value, amazon_id: &str) -> Option<i64> {
+    let key =
+        to_string(&metadata["props"]["pageProps"]["apolloState"][amazon_id]["work"]["__ref"])?;
+    metadata["props"]["pageProps"]["apolloState"][key]["editions"]["totalCount"].as_i64()
 }
 
+// The book's own legacy ID, falling back to the ID the page was requested with.
+fn extract_canonical_id(metadata: &Value, amazon_id: &str, goodreads_id: &str) -> String {
+    let id = &metadata["props"]["pageProps"]["apolloState"][amazon_id]["legacyId"];
+    id.as_i64()
+        .map(|i| i.to_string())
+        .or_else(|| to_string(id))
+        .unwrap_or_else(|| goodreads_id.to_string())
+}
+
+fn extract_popularity(metadata: &Value, amazon_id: &str) -> Option<i64> {
+    rating_stat(metadata, amazon_id, "ratingsCount")?.as_i64()
+}
+
+fn extract_ratings_histogram(metadata: &Value, amazon_id: &str) -> Option<[i64; 5]> {
+    let counts = rating_stat(metadata, amazon_id, "ratingsCountDist")?.as_array()?;
+    let counts = counts
+        .iter()
+        .map(Value::as_i64)
+        .collect::<Option<Vec<_>>>()?;
+    counts.try_into().ok()
+}
+
+// Prefers the edition's own rating stats and falls back to the work's, which is what Goodreads usually provides.
+fn rating_stat<'a>(metadata: &'a Value, amazon_id: &str, key: &str) -> Option<&'a Value> {
+    let apollo_state = &metadata["props"]["pageProps"]["apolloState"];
+    let edition_stat = &apollo_state[amazon_id]["stats"][key];
+    if !edition_stat.is_null() {
+        return Some(edition_stat);
+    }
+
+    let work_key = to_string(&apollo_state[amazon_id]["work"]["__ref"])?;
+    Some(&apollo_state[work_key]["stats"][key]).filter(|stat| !stat.is_null())
+}
+
+// Decodes HTML entities (e.g. `&amp;`), which Goodreads leaves in titles, names and publishers, and normalizes whitespace.
 fn to_string(value: &Value) -> Option<String> {
-    let re = Regex::new(r"\s{2,}").expect("Regex must be valid");
     value
         .as_str()
-        .map(str::trim)
-        .map(|s| re.replace_all(s, " ").to_string())
+        .map(decode_html_entities)
+        .map(|s| WHITESPACE_RE.replace_all(s.trim(), " ").to_string())
         .filter(|s| !s.is_empty())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn content_eq_test() {
+        let book = |image_url: &str| {
+            BookMetadata::new(
+                "The Last Olympian".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                vec![],
+                vec![],
+                None,
+                None,
+                None,
+                Some(image_url.to_string()),
+            )
+        };
+
+        let original = book(
+            "https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/4556058.jpg",
+        );
+        let reprocessed = book(
+            "https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1731000000i/4556058.jpg",
+        );
+        let other_cover = book(
+            "https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/28187.jpg",
+        );
+
+        assert_ne!(original, reprocessed);
+        assert!(original.content_eq(&reprocessed));
+        assert!(!original.content_eq(&other_cover));
+    }
+
+    #[test]
+    fn to_map_test() {
+        let mut metadata = BookMetadata::new(
+            "The Last Olympian".to_string(),
+            None,
+            None,
+            None,
+            Some(
+                DateTime::parse_from_rfc3339("2009-05-05T07:00:00Z")
+                    .unwrap()
+                    .to_utc(),
+            ),
+            None,
+            vec![
+                BookContributor::new("Rick Riordan".to_string(), "Author".to_string()),
+                BookContributor::new("Jesse Bernstein".to_string(), "Narrator".to_string()),
+            ],
+            vec!["Fantasy".to_string(), "Mythology".to_string()],
+            Some(BookSeries::new(
+                "Percy Jackson and the Olympians".to_string(),
+                5.0,
+            )),
+            Some(381),
+            None,
+            None,
+        );
+        metadata.shelves = vec![("to-read".to_string(), 120)];
+
+        let map = metadata.to_map();
+        assert_eq!(map["title"], "The Last Olympian");
+        assert_eq!(map["subtitle"], "");
+        assert_eq!(map["publication_date"], "2009-05-05T07:00:00+00:00");
+        assert_eq!(
+            map["contributors"],
+            "Rick Riordan (Author);Jesse Bernstein (Narrator)"
+        );
+        assert_eq!(map["genres"], "Fantasy;Mythology");
+        assert_eq!(map["series"], "Percy Jackson and the Olympians #5");
+        assert_eq!(map["page_count"], "381");
+        assert_eq!(map["shelves"], "to-read:120");
+        assert_eq!(map["description_truncated"], "false");
+        assert_eq!(map.len(), 30);
+    }
+
+    #[test]
+    fn with_title_test() {
+        let metadata = BookMetadata {
+            page_count: Some(381),
+            ..BookMetadata::with_title("The Last Olympian")
+        };
+
+        let mut expected = BookMetadata::new(
+            "The Last Olympian".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![],
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+        expected.page_count = Some(381);
+        assert_eq!(metadata, expected);
+    }
+
+    #[test]
+    fn translators_and_narrators_test() {
+        let metadata = BookMetadata::new(
+            "The Little Prince".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                BookContributor::new("Antoine de Saint-Exupéry".to_string(), "Author".to_string()),
+                BookContributor::new("Richard Howard".to_string(), "Translator".to_string()),
+                BookContributor::new("Viggo Mortensen".to_string(), "Reader".to_string()),
+                BookContributor::new("Katherine Woods".to_string(), " translator ".to_string()),
+            ],
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            metadata.translators(),
+            vec!["Richard Howard", "Katherine Woods"]
+        );
+        assert_eq!(metadata.narrators(), vec!["Viggo Mortensen"]);
+        assert_eq!(metadata.authors(), vec!["Antoine de Saint-Exupéry"]);
+        assert_eq!(
+            metadata.contributors_with_role(&ContributorRole::Illustrator),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let mut metadata = BookMetadata::new(
+            "The Last Olympian".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![
+                BookContributor::new("John Rocco".to_string(), "Illustrator".to_string()),
+                BookContributor::new("Rick Riordan".to_string(), "Author".to_string()),
+            ],
+            vec![],
+            Some(BookSeries::new(
+                "Percy Jackson and the Olympians".to_string(),
+                5.0,
+            )),
+            None,
+            None,
+            None,
+        );
+        metadata.publication_year = Some(2009);
+        assert_eq!(
+            metadata.to_string(),
+            "The Last Olympian by Rick Riordan (2009) — Percy Jackson and the Olympians #5"
+        );
+
+        metadata.contributors.remove(1);
+        metadata.publication_year = None;
+        metadata.series = None;
+        assert_eq!(metadata.to_string(), "The Last Olympian by John Rocco");
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn first_edition_test() {
+        let edition = |id: &str, isbn: Option<&str>, year: Option<i32>| {
+            let mut edition = BookEdition::new(
+                id.to_string(),
+                "The Last Olympian".to_string(),
+                None,
+                isbn.map(str::to_string),
+                None,
+            );
+            edition.publication_year = year;
+            edition
+        };
+
+        let editions = vec![
+            edition("1", Some("1423101472"), Some(2011)),
+            edition("2", None, Some(2008)),
+            edition("3", Some("1423101480"), Some(2009)),
+            edition("4", Some("1423101499"), None),
+        ];
+        assert_eq!(first_edition(&editions), Some(&editions[2]));
+        assert_eq!(first_edition(&editions[3..]), Some(&editions[3]));
+        assert_eq!(first_edition(&editions[1..2]), None);
+    }
+
+    #[test]
+    fn extract_amazon_id_test() {
+        let metadata = |key: &str| {
+            json!({"props": {"pageProps": {"apolloState": {"ROOT_QUERY": {
+                "getSimilarBooks({\"id\":\"kca://book/2\"})": {"__ref": "Book:2"},
+                key: {"__ref": "Book:1"}
+            }}}}})
+        };
+
+        let exact = metadata(r#"getBookByLegacyId({"legacyId":"4556058"})"#);
+        assert_eq!(extract_amazon_id(&exact, "4556058").unwrap(), "Book:1");
+
+        let reordered =
+            metadata(r#"getBookByLegacyId({"includeDrafts":false,"legacyId":4556058})"#);
+        assert_eq!(extract_amazon_id(&reordered, "4556058").unwrap(), "Book:1");
+
+        let other_book = metadata(r#"getBookByLegacyId({"legacyId":"45560580"})"#);
+        assert!(matches!(
+            extract_amazon_id(&other_book, "4556058"),
+            Err(ScraperError::MissingField { .. })
+        ));
+    }
+
+    #[test]
+    fn from_html_test() {
+        let html = r#"<html><body><script id="__NEXT_DATA__" type="application/json">
+            {"props": {"pageProps": {"apolloState": {
+                "ROOT_QUERY": {"getBookByLegacyId({\"legacyId\":\"4556058\"})": {"__ref": "Book:1"}},
+                "Book:1": {"title": "The Last Olympian", "details": {"numPages": 381}}
+            }}}}
+        </script></body></html>"#;
+
+        let metadata = BookMetadata::from_html(html, "4556058").unwrap();
+        assert_eq!(metadata.title, "The Last Olympian");
+        assert_eq!(metadata.page_count, Some(381));
+        assert_eq!(metadata.goodreads_id.as_deref(), Some("4556058"));
+        assert_eq!(metadata.redirected_from, None);
+        assert!(matches!(
+            BookMetadata::from_html("<html></html>", "4556058"),
+            Err(ScraperError::ScrapeError(_))
+        ));
+        assert!(matches!(
+            BookMetadata::from_html(html, "1"),
+            Err(ScraperError::MissingField { path, goodreads_id })
+                if path.ends_with(r#"getBookByLegacyId({"legacyId":"1"}).__ref"#) && goodreads_id == "1"
+        ));
+    }
+
+    #[test]
+    fn unavailable_book_test() {
+        let html = |apollo_state: &str| {
+            format!(
+                r#"<html><body><script id="__NEXT_DATA__" type="application/json">
+                {{"props": {{"pageProps": {{"apolloState": {apollo_state}}}}}}}
+                </script></body></html>"#
+            )
+        };
+
+        let deleted = html(r#"{"ROOT_QUERY": {"getBookByLegacyId({\"legacyId\":\"1\"})": null}}"#);
+        assert!(matches!(
+            BookMetadata::from_html(&deleted, "1"),
+            Err(ScraperError::Unavailable)
+        ));
+
+        let placeholder = html(
+            r#"{"ROOT_QUERY": {"getBookByLegacyId({\"legacyId\":\"1\"})": {"__ref": "Book:1"}},
+            "Book:1": {"__typename": "Book", "id": "kca://book/1"}}"#,
+        );
+        assert!(matches!(
+            BookMetadata::from_html(&placeholder, "1"),
+            Err(ScraperError::Unavailable)
+        ));
+
+        let broken = html(
+            r#"{"ROOT_QUERY": {"getBookByLegacyId({\"legacyId\":\"1\"})": {"__ref": "Book:1"}},
+            "Book:1": {"__typename": "Book", "details": {"numPages": 381}}}"#,
+        );
+        assert!(matches!(
+            BookMetadata::from_html(&broken, "1"),
+            Err(ScraperError::MissingField { .. })
+        ));
+    }
+
+    #[test]
+    fn extract_title_and_subtitle_test() {
+        let book =
+            |fields: Value| json!({"props": {"pageProps": {"apolloState": {"Book:1": fields}}}});
+        let extract = |metadata: &Value, keep_full_title: bool| {
+            let options = ExtractOptions {
+                keep_full_title,
+                ..ExtractOptions::default()
+            };
+            extract_title_and_subtitle(metadata, "1", "Book:1", &options).unwrap()
+        };
+
+        let colon = book(json!({"title": "2001: A Space Odyssey"}));
+        assert_eq!(
+            extract(&colon, false),
+            ("2001".to_string(), Some("A Space Odyssey".to_string()))
+        );
+        assert_eq!(
+            extract(&colon, true),
+            ("2001: A Space Odyssey".to_string(), None)
+        );
+
+        let dedicated = book(json!({
+            "title": "Sapiens: A Brief History of Humankind",
+            "subtitle": "A Brief History of Humankind"
+        }));
+        assert_eq!(
+            extract(&dedicated, true),
+            (
+                "Sapiens".to_string(),
+                Some("A Brief History of Humankind".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn extract_similar_books_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "ROOT_QUERY": {
+                "getSimilarBooks({\"id\":\"kca://book/1\",\"limit\":12})": {"edges": [
+                    {"node": {"__ref": "Book:2"}},
+                    {"node": {"__ref": "Book:3"}},
+                    {"node": {}}
+                ]}
+            },
+            "Book:2": {"legacyId": 28_187},
+            "Book:3": {"legacyId": "561456"}
+        }}}});
+
+        assert_eq!(
+            extract_similar_books(&metadata),
+            vec!["28187".to_string(), "561456".to_string()]
+        );
+        assert!(extract_similar_books(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn extract_duration_minutes_test() {
+        let book = |format: &str, details: Value| {
+            let mut details = details;
+            details["format"] = json!(format);
+            json!({"props": {"pageProps": {"apolloState": {"Book:1": {"details": details}}}}})
+        };
+
+        let audible = book(
+            "Audible Audio",
+            json!({"numPages": 712, "duration": "11 hours, 52 minutes"}),
+        );
+        assert_eq!(extract_duration_minutes(&audible, "Book:1"), Some(712));
+        assert_eq!(extract_page_count(&audible, "Book:1"), None);
+
+        let seconds = book("Audiobook", json!({"duration": 5400}));
+        assert_eq!(extract_duration_minutes(&seconds, "Book:1"), Some(90));
+
+        let hardcover = book("Hardcover", json!({"numPages": 381, "duration": "3 hours"}));
+        assert_eq!(extract_duration_minutes(&hardcover, "Book:1"), None);
+        assert_eq!(extract_page_count(&hardcover, "Book:1"), Some(381));
+
+        let joined = book("Audiobook", json!({"duration": "5 hrs and 3 mins"}));
+        assert_eq!(extract_duration_minutes(&joined, "Book:1"), Some(303));
+        let garbled = book("Audiobook", json!({"duration": "5 hrs dan 3 mins"}));
+        assert_eq!(extract_duration_minutes(&garbled, "Book:1"), None);
+    }
+
+    #[test]
+    fn extract_physical_details_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {"details": {
+            "format": "Hardcover",
+            "dimensions": "5.5 x 1.2 x 8.2 inches",
+            "weight": null
+        }}}}}});
+
+        assert_eq!(
+            extract_detail(&metadata, "Book:1", "format"),
+            Some("Hardcover".to_string())
+        );
+        assert_eq!(
+            extract_detail(&metadata, "Book:1", "dimensions"),
+            Some("5.5 x 1.2 x 8.2 inches".to_string())
+        );
+        assert_eq!(extract_detail(&metadata, "Book:1", "weight"), None);
+    }
+
+    #[test]
+    fn extract_work_details_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {"work": {"__ref": "Work:1"}},
+            "Work:1": {"legacyId": 4_551_489, "editions": {"totalCount": 164}}
+        }}}});
+
+        assert_eq!(
+            extract_work_id(&metadata, "Book:1"),
+            Some("4551489".to_string())
+        );
+        assert_eq!(extract_edition_count(&metadata, "Book:1"), Some(164));
+    }
+
+    #[test]
+    fn extract_popularity_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {"work": {"__ref": "Work:1"}},
+            "Book:2": {"stats": {"ratingsCount": 1_250}, "work": {"__ref": "Work:1"}},
+            "Work:1": {"stats": {"ratingsCount": 1_001_562}}
+        }}}});
+
+        assert_eq!(extract_popularity(&metadata, "Book:1"), Some(1_001_562));
+        assert_eq!(extract_popularity(&metadata, "Book:2"), Some(1_250));
+        assert_eq!(extract_popularity(&metadata, "Book:3"), None);
+    }
+
+    #[test]
+    fn extract_ratings_histogram_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {"work": {"__ref": "Work:1"}},
+            "Book:2": {"work": {"__ref": "Work:2"}},
+            "Work:1": {"stats": {"ratingsCountDist": [8_000, 14_000, 90_000, 280_000, 610_000]}},
+            "Work:2": {"stats": {"ratingsCountDist": [1, 2, 3]}}
+        }}}});
+
+        assert_eq!(
+            extract_ratings_histogram(&metadata, "Book:1"),
+            Some([8_000, 14_000, 90_000, 280_000, 610_000])
+        );
+        assert_eq!(extract_ratings_histogram(&metadata, "Book:2"), None);
+        assert_eq!(extract_ratings_histogram(&metadata, "Book:3"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn full_resolution_cover_url_test() {
+        assert_eq!(
+            full_resolution_cover_url(
+                "https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/4556058._SY475_.jpg"
+            ),
+            "https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/4556058.jpg"
+        );
+        assert_eq!(
+            full_resolution_cover_url("https://example.com/covers/1._SX318_SY475_.png"),
+            "https://example.com/covers/1.png"
+        );
+        assert_eq!(
+            full_resolution_cover_url("https://example.com/covers/4556058.jpg"),
+            "https://example.com/covers/4556058.jpg"
+        );
+    }
+
+    #[test]
+    fn extract_genres_keeps_ranking_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {"bookGenres": [
+            {"genre": {"name": "Fantasy"}},
+            {"genre": {"name": "Young Adult"}},
+            {"genre": {"name": "Fantasy"}},
+            {"genre": {}},
+            {"genre": {"name": "Audiobook"}}
+        ]}}}}});
+
+        assert_eq!(
+            extract_genres(&metadata, "Book:1", &[]),
+            vec![
+                "Fantasy".to_string(),
+                "Young Adult".to_string(),
+                "Audiobook".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_genres_blocklist_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {"bookGenres": [
+            {"genre": {"name": "Fantasy"}},
+            {"genre": {"name": "Currently Reading"}},
+            {"genre": {"name": "Audiobook"}},
+            {"genre": {"name": "Owned"}}
+        ]}}}}});
+
+        let blocklist: Vec<String> = DEFAULT_GENRE_BLOCKLIST
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            extract_genres(&metadata, "Book:1", &blocklist),
+            vec!["Fantasy".to_string()]
+        );
+        assert_eq!(
+            extract_genres(&metadata, "Book:1", &["audiobook".to_string()]),
+            vec![
+                "Fantasy".to_string(),
+                "Currently Reading".to_string(),
+                "Owned".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn is_truncated_test() {
+        assert!(is_truncated("When Percy learns that the Titans are…"));
+        assert!(is_truncated(
+            "<p>When Percy learns that the Titans are...</p>"
+        ));
+        assert!(!is_truncated("The battle for Olympus begins."));
+    }
+
+    #[test]
+    fn extract_original_publication_year_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {"work": {"__ref": "Work:1"}},
+            "Work:1": {"details": {"publicationTime": "1847"}}
+        }}}});
+
+        assert_eq!(
+            extract_original_publication_year(&metadata, "Book:1"),
+            Some(1847)
+        );
+        assert_eq!(
+            extract_original_publication_year(&json!({}), "Book:1"),
+            None
+        );
+    }
+
+    #[test]
+    fn extract_contributors_without_primary_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {
+                "primaryContributorEdge": null,
+                "secondaryContributorEdges": [
+                    {"role": "Editor", "node": {"__ref": "Contributor:1"}},
+                    {"role": "Contributor", "node": {"__ref": "Contributor:2"}},
+                    {"role": "Contributor", "node": {"__ref": "Contributor:3"}}
+                ]
+            },
+            "Contributor:1": {"name": "Ellen Datlow"},
+            "Contributor:2": {"name": "Neil Gaiman"},
+            "Contributor:3": {"name": "Unknown Author"}
+        }}}});
+
+        assert_eq!(
+            extract_contributors(&metadata, "Book:1"),
+            vec![
+                BookContributor::new("Ellen Datlow".to_string(), "Editor".to_string()),
+                BookContributor::new("Neil Gaiman".to_string(), "Contributor".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_contributors_orders_roles_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {
+                "primaryContributorEdge": {"role": "Author", "node": {"__ref": "Contributor:1"}},
+                "secondaryContributorEdges": [
+                    {"role": "Narrator", "node": {"__ref": "Contributor:2"}},
+                    {"role": "Illustrator", "node": {"__ref": "Contributor:3"}},
+                    {"role": "Translator", "node": {"__ref": "Contributor:4"}},
+                    {"role": "Author", "node": {"__ref": "Contributor:5"}},
+                    {"role": "Author", "node": {"__ref": "Contributor:6"}}
+                ]
+            },
+            "Contributor:1": {"name": "Terry Pratchett"},
+            "Contributor:2": {"name": "Stephen Briggs"},
+            "Contributor:3": {"name": "Josh Kirby"},
+            "Contributor:4": {"name": "Andreas Brandhorst"},
+            "Contributor:5": {"name": "Neil Gaiman"},
+            "Contributor:6": {"name": "Ian Stewart"}
+        }}}});
+
+        let names: Vec<String> = extract_contributors(&metadata, "Book:1")
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "Terry Pratchett",
+                "Neil Gaiman",
+                "Ian Stewart",
+                "Andreas Brandhorst",
+                "Josh Kirby",
+                "Stephen Briggs"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_series_position_test() {
+        assert!((parse_series_position("5") - 5.0).abs() < f32::EPSILON);
+        assert!((parse_series_position("#1.5") - 1.5).abs() < f32::EPSILON);
+        assert!((parse_series_position("1-3") - 1.0).abs() < f32::EPSILON);
+        assert!((parse_series_position("Book One") - 1.0).abs() < f32::EPSILON);
+        assert!((parse_series_position("Part II") - 2.0).abs() < f32::EPSILON);
+        assert!((parse_series_position("Third") - 3.0).abs() < f32::EPSILON);
+        assert!(parse_series_position("Prequel").abs() < f32::EPSILON);
+        assert!(parse_series_position("Companion").is_nan());
+    }
+
+    #[test]
+    fn extract_series_labels_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {
+            "Book:1": {"bookSeries": [{"userPosition": "Prequel", "series": {"__ref": "Series:1"}}]},
+            "Book:2": {"bookSeries": [{"userPosition": "#2", "series": {"__ref": "Series:1"}}]},
+            "Book:3": {"bookSeries": [{"userPosition": "", "series": {"__ref": "Series:1"}}]},
+            "Series:1": {
+                "title": "The Hunger Games",
+                "webUrl": "https://www.goodreads.com/series/73758-the-hunger-games",
+                "primaryWorksCount": 4
+            }
+        }}}});
+
+        let prequel = extract_series(&metadata, "Book:1").unwrap();
+        assert!(prequel.number.abs() < f32::EPSILON);
+        assert_eq!(prequel.label.as_deref(), Some("Prequel"));
+        assert_eq!(prequel.position().as_deref(), Some("Prequel"));
+        assert_eq!(prequel.id.as_deref(), Some("73758"));
+
+        let second = extract_series(&metadata, "Book:2").unwrap();
+        assert_eq!(second, BookSeries::new("The Hunger Games".to_string(), 2.0));
+        assert_eq!(second.label, None);
+        assert_eq!(second.position().as_deref(), Some("2"));
+        assert_eq!(second.total, Some(4));
+
+        let unnumbered = extract_series(&metadata, "Book:3").unwrap();
+        assert!(unnumbered.number.is_nan());
+        assert_eq!(unnumbered.position(), None);
+    }
+
+    #[test]
+    fn book_series_ord_test() {
+        let mut series = [
+            BookSeries::new("Discworld".to_string(), f32::NAN),
+            BookSeries::new("Discworld".to_string(), 2.0),
+            BookSeries::new("Discworld".to_string(), 1.5),
+            BookSeries::new("Alpha".to_string(), 2.0),
+            BookSeries::new("Discworld".to_string(), -0.0),
+        ];
+        series.sort();
+
+        let order: Vec<(&str, f32)> = series
+            .iter()
+            .map(|s| (s.title.as_str(), s.number))
+            .collect();
+        assert_eq!(
+            &order[..4],
+            &[
+                ("Discworld", 0.0),
+                ("Discworld", 1.5),
+                ("Alpha", 2.0),
+                ("Discworld", 2.0)
+            ]
+        );
+        assert!(series[4].number.is_nan());
+        assert_eq!(
+            BookSeries::new("Discworld".to_string(), 0.0)
+                .cmp(&BookSeries::new("Discworld".to_string(), -0.0)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_shelves_test() {
+        let document = Html::parse_document(
+            r#"<div class="shelfStat">
+                <div><a class="mediumText actionLinkLite" href="/shelf/show/fantasy">fantasy</a></div>
+                <div class="smallText">41,617 people</div>
+            </div>
+            <div class="shelfStat">
+                <div><a class="mediumText actionLinkLite" href="/shelf/show/greek-mythology">greek-mythology</a></div>
+                <div class="smallText">
+                    1,204 people
+                </div>
+            </div>"#,
+        );
+
+        assert_eq!(
+            parse_shelves(&document).unwrap(),
+            vec![
+                ("fantasy".to_string(), 41_617),
+                ("greek-mythology".to_string(), 1_204)
+            ]
+        );
+        assert!(parse_shelves(&Html::parse_document("")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn book_series_hash_test() {
+        let series = std::collections::HashSet::from([
+            BookSeries::new("Discworld".to_string(), 0.0),
+            BookSeries::new("Discworld".to_string(), -0.0),
+            BookSeries::new("Discworld".to_string(), 1.5),
+        ]);
+        assert_eq!(series.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_series_test() {
+        let document = Html::parse_document(
+            r#"<div class="listWithDividers">
+                <div class="listWithDividers__item"><h3 class="gr-h3">Book 1</h3>
+                    <a itemprop="url" href="https://www.goodreads.com/book/show/28187.The_Lightning_Thief">The Lightning Thief</a></div>
+                <div class="listWithDividers__item"><h3 class="gr-h3">Book 1-3</h3>
+                    <a href="/book/show/3165162-percy-jackson-and-the-olympians">Boxed Set</a></div>
+                <div class="listWithDividers__item"><h3 class="gr-h3">Book 4.5</h3>
+                    <a href="/book/show/3636124-the-demigod-files">The Demigod Files</a></div>
+            </div>"#,
+        );
+
+        assert_eq!(
+            parse_series(&document).unwrap(),
+            vec![(1.0, "28187".to_string()), (4.5, "3636124".to_string())]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "net")]
+    fn parse_quotes_test() {
+        let document = Html::parse_document(
+            r#"<div class="quote"><div class="quoteText">
+                  “The real world is where the monsters are.”
+                <br>  ―
+                <span class="authorOrTitle">Rick Riordan,</span>
+            </div></div>"#,
+        );
+
+        assert_eq!(
+            parse_quotes(&document).unwrap(),
+            vec!["The real world is where the monsters are.".to_string()]
+        );
+        assert!(parse_quotes(&Html::parse_document("")).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "net")]
+    async fn fetch_metadata_selected_fields_test() {
+        let options = ExtractOptions {
+            fields: Some(vec![MetadataField::ImageUrl, MetadataField::Contributors]),
+            ..Default::default()
+        };
+        let metadata = fetch_metadata(&GoodreadsClient::default(), "4556058", &options)
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.title, "The Last Olympian");
+        assert_eq!(metadata.contributors.len(), 1);
+        assert!(metadata.image_url.is_some());
+        assert!(metadata.description.is_none());
+        assert!(metadata.genres.is_empty());
+        assert!(metadata.series.is_none());
+    }
+
+    #[test]
+    fn extract_publication_date_test() {
+        let book = |time: Value| {
+            json!({"props": {"pageProps": {"apolloState": {"Book:1": {
+                "details": {"publicationTime": time}
+            }}}}})
+        };
+        let expected = DateTime::parse_from_rfc3339("2009-05-05T07:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        assert_eq!(
+            extract_publication_date(&book(json!(1_241_506_800_000_i64)), "Book:1"),
+            Some(expected)
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!("2009-05-05T07:00:00Z")), "Book:1"),
+            Some(expected)
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!("May 5, 2009")), "Book:1"),
+            Some(
+                DateTime::parse_from_rfc3339("2009-05-05T00:00:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!(-3_786_825_600_000_i64)), "Book:1"),
+            Some(
+                DateTime::parse_from_rfc3339("1850-01-01T00:00:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!(-84_358_800_000_000_i64)), "Book:1")
+                .map(|date| date.year()),
+            Some(-704)
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!("700 BC")), "Book:1"),
+            NaiveDate::from_ymd_opt(-699, 1, 1)
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!("1847")), "Book:1"),
+            Some(
+                DateTime::parse_from_rfc3339("1847-01-01T00:00:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!("sometime")), "Book:1"),
+            None
+        );
+        assert_eq!(
+            extract_publication_date(&book(json!({"year": 2009})), "Book:1"),
+            None
+        );
+        assert_eq!(extract_publication_date(&book(Value::Null), "Book:1"), None);
+
+        assert_eq!(
+            extract_publication_timestamp(&book(json!(-3_786_825_600_000_i64)), "Book:1"),
+            Some(-3_786_825_600_000)
+        );
+        assert_eq!(
+            extract_publication_timestamp(&book(json!("1847")), "Book:1"),
+            None
+        );
+        assert_eq!(
+            extract_publication_timestamp(&book(json!("May 5, 2009")), "Book:1"),
+            None
+        );
+    }
+
+    #[test]
+    fn language_code_test() {
+        assert_eq!(language_code("English").as_deref(), Some("en"));
+        assert_eq!(language_code("Spanish; Castilian").as_deref(), Some("es"));
+        assert_eq!(language_code(" french ").as_deref(), Some("fr"));
+        assert_eq!(
+            language_code("Greek, Modern (1453-)").as_deref(),
+            Some("el")
+        );
+        assert_eq!(language_code("Klingon"), None);
+    }
+
+    #[test]
+    fn normalized_name_test() {
+        let normalized = |name: &str| {
+            BookContributor::new(name.to_string(), "Author".to_string()).normalized_name()
+        };
+
+        assert_eq!(normalized("R. F. Kuang"), "R.F. Kuang");
+        assert_eq!(normalized(" R.F.  Kuang "), "R.F. Kuang");
+        assert_eq!(normalized("J. R. R. Tolkien"), "J.R.R. Tolkien");
+        assert_eq!(
+            normalized("Martin Luther King Jr."),
+            "Martin Luther King Jr."
+        );
+        assert_eq!(normalized("Ursula K. Le Guin"), "Ursula K. Le Guin");
+    }
+
+    #[test]
+    fn parse_contributor_role_test() {
+        let translator =
+            BookContributor::new("Gregory Rabassa".to_string(), "Translator".to_string());
+        assert_eq!(translator.parsed_role(), ContributorRole::Translator);
+        assert_eq!(
+            ContributorRole::from("Cover Design"),
+            ContributorRole::CoverArtist
+        );
+        assert_eq!(
+            ContributorRole::from("Photographer"),
+            ContributorRole::Other("Photographer".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_description_decodes_entities_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {
+            "description": "Don&#39;t   panic &amp; carry a &quot;towel&quot;."
+        }}}}});
+
+        assert_eq!(
+            extract_description(&metadata, "Book:1", false),
+            Some("Don't panic & carry a \"towel\".".to_string())
+        );
+    }
+
+    #[test]
+    fn work_description_fallback_test() {
+        let page = |edition_description: Value| {
+            json!({"props": {"pageProps": {"apolloState": {
+                "ROOT_QUERY": {"getBookByLegacyId({\"legacyId\":\"1\"})": {"__ref": "Book:1"}},
+                "Book:1": {"title": "Jane Eyre", "description": edition_description, "work": {"__ref": "Work:1"}},
+                "Work:1": {"description": "Orphaned as a child, Jane has felt an outcast her whole young life."}
+            }}}})
+        };
+
+        let metadata = parse_metadata(&page(Value::Null), "1", &ExtractOptions::default()).unwrap();
+        assert_eq!(metadata.description, metadata.work_description);
+        assert!(metadata.work_description.is_some());
+
+        let metadata = parse_metadata(
+            &page(json!("A Penguin Classics edition.")),
+            "1",
+            &ExtractOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            metadata.description.as_deref(),
+            Some("A Penguin Classics edition.")
+        );
+        assert_eq!(
+            metadata.work_description.as_deref(),
+            Some("Orphaned as a child, Jane has felt an outcast her whole young life.")
+        );
+    }
+
+    #[test]
+    fn extract_description_preserve_whitespace_test() {
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {
+            "description": "  Chapter One\n\n    /\\_/\\\n   ( o.o )  &amp; more\n"
+        }}}}});
+
+        assert_eq!(
+            extract_description(&metadata, "Book:1", true),
+            Some("Chapter One\n\n    /\\_/\\\n   ( o.o )  & more".to_string())
+        );
+        assert_eq!(
+            extract_description(&metadata, "Book:1", false),
+            Some("Chapter One /\\_/\\ ( o.o ) & more".to_string())
+        );
+    }
+
+    #[test]
+    fn to_string_decodes_entities_test() {
+        assert_eq!(
+            to_string(&json!(
+                "  Alice &amp; the   Looking&#8209;Glass &mdash; &#8220;Annotated&#8221; "
+            )),
+            Some("Alice & the Looking\u{2011}Glass \u{2014} \u{201c}Annotated\u{201d}".to_string())
+        );
+        assert_eq!(to_string(&json!("&#32;")), None);
+
+        let metadata = json!({"props": {"pageProps": {"apolloState": {"Book:1": {"details": {
+            "publisher": "Simon &amp; Schuster"
+        }}}}}});
+        assert_eq!(
+            extract_publisher(&metadata, "Book:1"),
+            Some("Simon & Schuster".to_string())
+        );
+    }
 
     #[tokio::test]
+    #[cfg(feature = "net")]
     async fn fetch_metadata_test() {
         let expected_series = Some(BookSeries::new(
             "Percy Jackson and the Olympians".to_string(),
@@ -342,7 +2595,7 @@ mod tests {
             "Urban Fantasy".to_string(),
             "Childrens".to_string(),
         ];
-        let expected_metadata = BookMetadata::new(
+        let mut expected_metadata = BookMetadata::new(
             "The Last Olympian".to_string(),
             None,
             Some("All year the half-bloods have been preparing for battle against the Titans, knowing the odds of victory are grim. \
@@ -363,7 +2616,41 @@ mod tests {
             Some("https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/4556058.jpg".to_string()),
         );
 
-        let metadata = fetch_metadata("4556058").await.unwrap();
-        assert_eq!(metadata, expected_metadata);
+        let metadata = fetch_metadata(
+            &GoodreadsClient::default(),
+            "4556058",
+            &ExtractOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        // Work identifiers and edition counts are not pinned, only checked for presence. Physical details, recommendations,
+        // the work description, popularity and rating counts are not pinned either.
+        assert!(metadata.work_id.is_some());
+        assert!(metadata.edition_count.is_some_and(|count| count > 0));
+        expected_metadata.work_id.clone_from(&metadata.work_id);
+        expected_metadata.edition_count = metadata.edition_count;
+        expected_metadata.format = Some("Hardcover".to_string());
+        expected_metadata.publication_year = Some(2009);
+        expected_metadata
+            .dimensions
+            .clone_from(&metadata.dimensions);
+        expected_metadata.weight.clone_from(&metadata.weight);
+        expected_metadata
+            .similar_books
+            .clone_from(&metadata.similar_books);
+        expected_metadata
+            .work_description
+            .clone_from(&metadata.work_description);
+        expected_metadata.edition_popularity = metadata.edition_popularity;
+        expected_metadata.ratings_histogram = metadata.ratings_histogram;
+        expected_metadata.goodreads_id = Some("4556058".to_string());
+        expected_metadata.publication_timestamp_ms = Some(1_241_506_800_000);
+        expected_metadata.language_code = Some("en".to_string());
+
+        assert!(
+            metadata.content_eq(&expected_metadata),
+            "{metadata:#?} != {expected_metadata:#?}"
+        );
     }
 }
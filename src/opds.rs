@@ -0,0 +1,181 @@
+use crate::metadata_fetcher::{BookMetadata, ContributorRole};
+use chrono::{SecondsFormat, Utc};
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use std::fmt::Write;
+
+impl BookMetadata {
+    /// Renders the book as an OPDS 1.2 (Atom) acquisition feed `<entry>`, with its title, authors and other
+    /// contributors, summary, publisher, issue date, genres as categories, ISBN and cover link.
+    ///
+    /// The entry ID is the ISBN as a `urn:isbn:` URN, or the book's Goodreads URL when the book has no ISBN.
+    /// `updated` is the time of the call. Acquisition links are left to the catalog serving the entry.
+    pub fn to_opds_entry(&self) -> String {
+        let mut entry = String::from(
+            r#"<entry xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/terms/" xmlns:opds="http://opds-spec.org/2010/catalog">"#,
+        );
+
+        let title = match &self.subtitle {
+            Some(subtitle) => format!("{}: {subtitle}", self.title),
+            None => self.title.clone(),
+        };
+        element(&mut entry, "title", &title);
+
+        let work_url = self
+            .work_id
+            .as_ref()
+            .map(|work_id| format!("https://www.goodreads.com/work/show/{work_id}"));
+        let id = match (&self.isbn, &self.goodreads_id, &work_url) {
+            (Some(isbn), _, _) => format!("urn:isbn:{isbn}"),
+            (None, Some(goodreads_id), _) => {
+                format!("https://www.goodreads.com/book/show/{goodreads_id}")
+            }
+            (None, None, Some(work_url)) => work_url.clone(),
+            // Only books built by hand lack all identifiers.
+            (None, None, None) => format!("urn:goodreads:title:{}", urn_escape(&self.title)),
+        };
+        element(&mut entry, "id", &id);
+        element(
+            &mut entry,
+            "updated",
+            &Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        );
+
+        for contributor in &self.contributors {
+            let tag = match contributor.parsed_role() {
+                ContributorRole::Author => "author",
+                _ => "contributor",
+            };
+            let _ = write!(
+                entry,
+                "<{tag}><name>{}</name></{tag}>",
+                encode_text(&contributor.name)
+            );
+        }
+
+        if let Some(isbn) = &self.isbn {
+            element(&mut entry, "dc:identifier", &format!("urn:isbn:{isbn}"));
+        }
+        if let Some(publisher) = &self.publisher {
+            element(&mut entry, "dc:publisher", publisher);
+        }
+        if let Some(date) = self.publication_date {
+            element(
+                &mut entry,
+                "dc:issued",
+                &date.format("%Y-%m-%d").to_string(),
+            );
+        }
+
+        for genre in &self.genres {
+            let genre = encode_double_quoted_attribute(genre);
+            let _ = write!(entry, r#"<category term="{genre}" label="{genre}"/>"#);
+        }
+
+        if let Some(description) = &self.description {
+            element(&mut entry, "summary", description);
+        }
+
+        if let Some(image_url) = &self.image_url {
+            let image_url = encode_double_quoted_attribute(image_url);
+            let _ = write!(
+                entry,
+                r#"<link rel="http://opds-spec.org/image" href="{image_url}" type="image/jpeg"/>"#
+            );
+        }
+        if let Some(work_url) = &work_url {
+            let work_url = encode_double_quoted_attribute(work_url);
+            let _ = write!(
+                entry,
+                r#"<link rel="alternate" href="{work_url}" type="text/html"/>"#
+            );
+        }
+
+        entry.push_str("</entry>");
+        entry
+    }
+}
+
+fn element(entry: &mut String, tag: &str, text: &str) {
+    let _ = write!(entry, "<{tag}>{}</{tag}>", encode_text(text));
+}
+
+// Percent-encodes everything but unreserved characters, as URNs require.
+fn urn_escape(text: &str) -> String {
+    text.bytes().fold(String::new(), |mut escaped, byte| {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            escaped.push(char::from(byte));
+        } else {
+            let _ = write!(escaped, "%{byte:02X}");
+        }
+        escaped
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::metadata_fetcher::{BookContributor, BookMetadata};
+    use chrono::DateTime;
+
+    #[test]
+    fn to_opds_entry_test() {
+        let mut metadata = BookMetadata::new(
+            "Pride & Prejudice".to_string(),
+            None,
+            Some("It is a truth <universally> acknowledged.".to_string()),
+            Some("Penguin".to_string()),
+            Some(
+                DateTime::parse_from_rfc3339("2002-12-31T08:00:00Z")
+                    .unwrap()
+                    .to_utc(),
+            ),
+            Some("9780141439518".to_string()),
+            vec![
+                BookContributor::new("Jane Austen".to_string(), "Author".to_string()),
+                BookContributor::new("Anna Quindlen".to_string(), "Introduction".to_string()),
+            ],
+            vec!["Classics".to_string()],
+            None,
+            Some(279),
+            Some("English".to_string()),
+            Some("https://example.com/cover.jpg?a=1&b=2".to_string()),
+        );
+        metadata.work_id = Some("3060926".to_string());
+
+        let entry = metadata.to_opds_entry();
+        assert!(entry.starts_with(r#"<entry xmlns="http://www.w3.org/2005/Atom""#));
+        assert!(entry.ends_with("</entry>"));
+        assert!(entry.contains("<title>Pride &amp; Prejudice</title>"));
+        assert!(entry.contains("<id>urn:isbn:9780141439518</id>"));
+        assert!(entry.contains("<author><name>Jane Austen</name></author>"));
+        assert!(entry.contains("<contributor><name>Anna Quindlen</name></contributor>"));
+        assert!(entry.contains("<dc:issued>2002-12-31</dc:issued>"));
+        assert!(
+            entry.contains("<summary>It is a truth &lt;universally&gt; acknowledged.</summary>")
+        );
+        assert!(entry.contains(r#"<category term="Classics" label="Classics"/>"#));
+        assert!(entry.contains(r#"href="https://example.com/cover.jpg?a=1&amp;b=2""#));
+        assert!(entry.contains(r#"href="https://www.goodreads.com/work/show/3060926""#));
+
+        metadata.isbn = None;
+        metadata.goodreads_id = Some("1885".to_string());
+        assert!(
+            metadata
+                .to_opds_entry()
+                .contains("<id>https://www.goodreads.com/book/show/1885</id>")
+        );
+
+        metadata.goodreads_id = None;
+        assert!(
+            metadata
+                .to_opds_entry()
+                .contains("<id>https://www.goodreads.com/work/show/3060926</id>")
+        );
+
+        metadata.work_id = None;
+        assert!(
+            metadata
+                .to_opds_entry()
+                .contains("<id>urn:goodreads:title:Pride%20%26%20Prejudice</id>")
+        );
+    }
+}
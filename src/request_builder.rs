@@ -1,105 +1,731 @@
 use crate::{
+    client::GoodreadsClient,
     errors::ScraperError,
+    goodreads_id::GoodreadsId,
     goodreads_id_fetcher::{
-        fetch_id_from_isbn, fetch_id_from_title, fetch_id_from_title_and_author, verify_id_exists,
+        SearchOptions, SearchOutcome, SearchResult, fetch_editions, fetch_id_from_asin,
+        fetch_id_from_author_books, fetch_id_from_isbn, fetch_id_from_title,
+        fetch_id_from_title_and_author, fetch_id_from_work_id, fetch_isbn_match, match_confidence,
+        search_author, search_author_books, search_title, search_title_and_author,
+        verify_id_exists,
+    },
+    isbn::Isbn,
+    metadata_fetcher::{
+        BookMetadata, DEFAULT_GENRE_BLOCKLIST, ExtractOptions, MetadataField, extract_page_work_id,
+        fetch_metadata, fetch_metadata_and_work_id,
     },
-    metadata_fetcher::{BookMetadata, fetch_metadata},
 };
+use log::warn;
+use std::sync::Arc;
+
+const DEFAULT_MAX_EDITIONS: usize = 20;
 
 pub trait RequestState {}
 pub struct EmptyState;
-pub struct IdState(String);
-pub struct IsbnState(String);
-pub struct TitleState(String);
-pub struct TitleWithAuthorState(String, String);
+pub struct IdState(Result<GoodreadsId, ScraperError>, bool);
+pub struct IsbnState(Vec<String>, bool);
+pub struct AsinState(String);
+pub struct WorkIdState(String);
+pub struct TitleState(String, SearchOptions);
+pub struct TitleWithAuthorState(String, String, SearchOptions);
+pub struct TitleWithAuthorIdState(String, String, SearchOptions);
+pub struct AuthorState(String);
+#[derive(Default)]
+pub struct DynamicState {
+    id: Option<String>,
+    isbn: Option<String>,
+    title: Option<String>,
+    author: Option<String>,
+    search_options: SearchOptions,
+}
 
 impl RequestState for EmptyState {}
 impl RequestState for IdState {}
 impl RequestState for IsbnState {}
+impl RequestState for AsinState {}
+impl RequestState for WorkIdState {}
 impl RequestState for TitleState {}
 impl RequestState for TitleWithAuthorState {}
+impl RequestState for TitleWithAuthorIdState {}
+impl RequestState for AuthorState {}
+impl RequestState for DynamicState {}
+
+/// States that resolve a book through a title search.
+pub trait SearchState: RequestState {
+    fn search_options(&mut self) -> &mut SearchOptions;
+}
+
+impl SearchState for TitleState {
+    fn search_options(&mut self) -> &mut SearchOptions {
+        &mut self.1
+    }
+}
+
+impl SearchState for TitleWithAuthorState {
+    fn search_options(&mut self) -> &mut SearchOptions {
+        &mut self.2
+    }
+}
+
+impl SearchState for TitleWithAuthorIdState {
+    fn search_options(&mut self) -> &mut SearchOptions {
+        &mut self.2
+    }
+}
+
+impl SearchState for DynamicState {
+    fn search_options(&mut self) -> &mut SearchOptions {
+        &mut self.search_options
+    }
+}
 
 /// Builder for constructing a metadata request.
+#[must_use]
 pub struct MetadataRequestBuilder<T: RequestState> {
     state: T,
+    client: GoodreadsClient,
+    options: ExtractOptions,
 }
 
 impl Default for MetadataRequestBuilder<EmptyState> {
     fn default() -> Self {
-        MetadataRequestBuilder::new()
+        MetadataRequestBuilder::from_client(GoodreadsClient::default())
     }
 }
 
-impl MetadataRequestBuilder<EmptyState> {
-    fn new() -> Self {
-        MetadataRequestBuilder { state: EmptyState }
+/// Shorthand for `MetadataRequestBuilder::default().with_isbn(isbn)`.
+pub fn by_isbn(isbn: impl AsRef<str>) -> MetadataRequestBuilder<IsbnState> {
+    MetadataRequestBuilder::default().with_isbn(isbn)
+}
+
+/// Shorthand for `MetadataRequestBuilder::default().with_id(id)`.
+pub fn by_id<I>(id: I) -> MetadataRequestBuilder<IdState>
+where
+    I: TryInto<GoodreadsId>,
+    I::Error: Into<ScraperError>,
+{
+    MetadataRequestBuilder::default().with_id(id)
+}
+
+/// Shorthand for `MetadataRequestBuilder::default().with_title(title)`. Chain `with_author` to narrow the search.
+pub fn by_title(title: &str) -> MetadataRequestBuilder<TitleState> {
+    MetadataRequestBuilder::default().with_title(title)
+}
+
+impl<T: RequestState> MetadataRequestBuilder<T> {
+    /// Only extracts the given fields, leaving the others as `None` or empty. The title is always extracted.
+    pub fn with_fields(mut self, fields: &[MetadataField]) -> Self {
+        self.options.fields = Some(fields.to_vec());
+        self
+    }
+
+    /// Whether to split titles on their first colon into title and subtitle (the default) when Goodreads
+    /// has no dedicated subtitle. Disable it to keep titles like "2001: A Space Odyssey" whole.
+    pub fn with_subtitle_splitting(mut self, enabled: bool) -> Self {
+        self.options.keep_full_title = !enabled;
+        self
+    }
+
+    /// Also fetches the user shelves of the book's work into `BookMetadata::shelves`, at the cost of an extra request.
+    pub fn with_shelves(mut self) -> Self {
+        self.options.shelves = true;
+        self
+    }
+
+    /// Also looks up the ISBN of the work's earliest published edition into `BookMetadata::first_edition_isbn`,
+    /// at the cost of an extra request. Only the first 100 editions listed by Goodreads are considered.
+    pub fn with_first_edition(mut self) -> Self {
+        self.options.first_edition = true;
+        self
+    }
+
+    /// Scrapes an edition in the given language (e.g. "French") instead of the resolved one, when the work has one.
+    /// Falls back to the resolved edition otherwise. Costs up to two additional requests.
+    pub fn with_preferred_language(mut self, language: &str) -> Self {
+        self.options.language = Some(language.to_string());
+        self
+    }
+
+    /// Leaves the given shelf names out of `genres`, compared case-insensitively. Replaces any previous blocklist.
+    pub fn with_genre_blocklist(mut self, blocklist: &[&str]) -> Self {
+        self.options.genre_blocklist = blocklist.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Leaves the shelves in `DEFAULT_GENRE_BLOCKLIST`, such as "to-read" or "owned", out of `genres`.
+    pub fn with_default_genre_blocklist(self) -> Self {
+        self.with_genre_blocklist(DEFAULT_GENRE_BLOCKLIST)
+    }
+
+    /// Keeps the description's line breaks and runs of spaces, e.g. for monospace rendering, instead of
+    /// collapsing them into single spaces (the default).
+    pub fn with_preserve_whitespace(mut self, preserve: bool) -> Self {
+        self.options.preserve_whitespace = preserve;
+        self
     }
 
-    pub fn with_id(self, id: &str) -> MetadataRequestBuilder<IdState> {
+    fn transition<S: RequestState>(self, state: S) -> MetadataRequestBuilder<S> {
         MetadataRequestBuilder {
-            state: IdState(id.to_string()),
+            state,
+            client: self.client,
+            options: self.options,
         }
     }
+}
 
-    pub fn with_isbn(self, isbn: &str) -> MetadataRequestBuilder<IsbnState> {
+impl MetadataRequestBuilder<EmptyState> {
+    pub(crate) fn from_client(client: GoodreadsClient) -> Self {
         MetadataRequestBuilder {
-            state: IsbnState(isbn.to_string()),
+            state: EmptyState,
+            client,
+            options: ExtractOptions::default(),
         }
     }
 
+    /// Looks a book up by Goodreads ID, given as a string or a `GoodreadsId`. A malformed ID makes `execute`
+    /// fail with the conversion error, `ScraperError::ParseError` for strings, without sending any request.
+    pub fn with_id<I>(self, id: I) -> MetadataRequestBuilder<IdState>
+    where
+        I: TryInto<GoodreadsId>,
+        I::Error: Into<ScraperError>,
+    {
+        let id = id.try_into().map_err(Into::into);
+        self.transition(IdState(id, false))
+    }
+
+    /// Looks a book up by ISBN, given as a string or an `Isbn`. Separators such as hyphens are ignored.
+    pub fn with_isbn(self, isbn: impl AsRef<str>) -> MetadataRequestBuilder<IsbnState> {
+        self.transition(IsbnState(vec![isbn.as_ref().to_string()], false))
+    }
+
+    /// Looks a book up by several ISBNs of the same edition, e.g. its ISBN-10 and ISBN-13, trying each in order
+    /// until one resolves. Goodreads sometimes only indexes one of them. `execute` returns `Ok(None)` only when
+    /// none resolves, and fails with `ScraperError::ParseError` when `isbns` is empty.
+    pub fn with_isbns(self, isbns: &[&str]) -> MetadataRequestBuilder<IsbnState> {
+        let isbns = isbns.iter().map(ToString::to_string).collect();
+        self.transition(IsbnState(isbns, false))
+    }
+
+    /// Looks a book up by its work ID, fetching the metadata of the work's default edition.
+    pub fn with_work_id(self, work_id: &str) -> MetadataRequestBuilder<WorkIdState> {
+        self.transition(WorkIdState(work_id.to_string()))
+    }
+
+    /// Looks a book up by its Amazon ASIN, such as the identifiers found in Kindle library exports.
+    pub fn with_asin(self, asin: &str) -> MetadataRequestBuilder<AsinState> {
+        self.transition(AsinState(asin.to_string()))
+    }
+
     pub fn with_title(self, title: &str) -> MetadataRequestBuilder<TitleState> {
-        MetadataRequestBuilder {
-            state: TitleState(title.to_string()),
-        }
+        self.transition(TitleState(title.to_string(), SearchOptions::default()))
+    }
+
+    /// Searches for the books of an author, without a title.
+    pub fn with_author_only(self, author: &str) -> MetadataRequestBuilder<AuthorState> {
+        self.transition(AuthorState(author.to_string()))
+    }
+
+    /// Switches to a request whose criteria are set at runtime, see `MetadataRequestBuilder::dynamic`.
+    pub fn into_dynamic(self) -> MetadataRequestBuilder<DynamicState> {
+        self.transition(DynamicState::default())
+    }
+}
+
+impl<T: SearchState> MetadataRequestBuilder<T> {
+    /// Prefers the search candidate published in `year` when several books match.
+    pub fn with_year(mut self, year: i32) -> Self {
+        self.state.search_options().year = Some(year);
+        self
+    }
+
+    /// Ignores search candidates whose average rating is below `rating`.
+    pub fn with_min_rating(mut self, rating: f32) -> Self {
+        self.state.search_options().min_rating = Some(rating);
+        self
+    }
+
+    /// Ignores search candidates with fewer than `count` ratings.
+    pub fn with_min_ratings_count(mut self, count: i64) -> Self {
+        self.state.search_options().min_ratings_count = Some(count);
+        self
+    }
+
+    /// Requires candidate titles to equal the searched title, ignoring case, punctuation and a series suffix,
+    /// instead of merely containing it. Useful for short titles such as "It".
+    pub fn with_exact_title(mut self, exact: bool) -> Self {
+        self.state.search_options().exact_title = exact;
+        self
+    }
+
+    /// Prefers the search candidate listed in `series` (e.g. "Percy Jackson and the Olympians") when several
+    /// books share the title. Falls back to the usual pick when no candidate is in the series.
+    pub fn with_series(mut self, series: &str) -> Self {
+        self.state.search_options().series = Some(series.to_string());
+        self
+    }
+
+    /// Only considers the first `max` search results, in Goodreads' ranking order, instead of all of them.
+    /// Ignoring the long tail of deep, loosely related results makes ambiguous queries more precise.
+    pub fn with_max_candidates(mut self, max: usize) -> Self {
+        self.state.search_options().max_candidates = Some(max);
+        self
+    }
+
+    /// Replaces the built-in title and author matching with `matcher`, called with the found title, the found
+    /// authors (joined with ", "), the searched title and the searched author, if any. Rating and year options
+    /// still apply, but the fallback to a single strong title match is disabled.
+    pub fn with_matcher(
+        mut self,
+        matcher: impl Fn(&str, &str, &str, Option<&str>) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.state.search_options().matcher = Some(Arc::new(matcher));
+        self
+    }
+
+    /// Makes `execute_all` return every edition of the matched book's work instead of only the matched edition.
+    /// This makes one request per edition, see `with_max_editions`.
+    pub fn all_editions(mut self, all: bool) -> Self {
+        self.state.search_options().all_editions = all;
+        self
+    }
+
+    /// Caps how many editions `execute_all` fetches, including the matched one. Defaults to 20.
+    pub fn with_max_editions(mut self, max: usize) -> Self {
+        self.state.search_options().max_editions = Some(max);
+        self
     }
 }
 
 impl MetadataRequestBuilder<TitleState> {
     pub fn with_author(self, author: &str) -> MetadataRequestBuilder<TitleWithAuthorState> {
+        let TitleState(title, search_options) = self.state;
         MetadataRequestBuilder {
-            state: TitleWithAuthorState(self.state.0, author.to_string()),
+            state: TitleWithAuthorState(title, author.to_string(), search_options),
+            client: self.client,
+            options: self.options,
+        }
+    }
+
+    /// Looks the title up in the book list of the author with the given Goodreads ID, instead of searching
+    /// the whole site. Far more accurate for prolific authors or common titles.
+    pub fn with_author_id(self, author_id: &str) -> MetadataRequestBuilder<TitleWithAuthorIdState> {
+        let TitleState(title, search_options) = self.state;
+        MetadataRequestBuilder {
+            state: TitleWithAuthorIdState(title, author_id.to_string(), search_options),
+            client: self.client,
+            options: self.options,
         }
     }
 
     pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
-        let title = &self.state.0;
-        let goodreads_id = fetch_id_from_title(title).await?;
-        match goodreads_id {
-            Some(id) => Ok(Some(fetch_metadata(&id).await?)),
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
             None => Ok(None),
         }
     }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        fetch_id_from_title(&self.client, &self.state.0, &self.state.1).await
+    }
+
+    /// Like `execute`, but also returns how confident the title match is, from 0.0 to 1.0 (an exact title).
+    pub async fn execute_with_confidence(
+        &self,
+    ) -> Result<Option<(BookMetadata, f32)>, ScraperError> {
+        let SearchOutcome::Found(result) = self.search().await? else {
+            return Ok(None);
+        };
+
+        let confidence = match_confidence(&result, &self.state.0, None);
+        let metadata = fetch_metadata(&self.client, &result.goodreads_id, &self.options).await?;
+        Ok(Some((metadata, confidence)))
+    }
+
+    /// Fetches the matched edition, followed by the work's other editions when `all_editions` is set.
+    /// Returns an empty list when no book matches.
+    pub async fn execute_all(&self) -> Result<Vec<BookMetadata>, ScraperError> {
+        match self.search().await?.goodreads_id() {
+            Some(id) => fetch_all_editions(&self.client, id, &self.options, &self.state.1).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Runs only the title search, reporting whether it found a match, no results, or only non-matching candidates.
+    pub async fn search(&self) -> Result<SearchOutcome, ScraperError> {
+        search_title(&self.client, &self.state.0, &self.state.1).await
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
 }
 
 impl MetadataRequestBuilder<IdState> {
+    /// When `skip` is true, fetches the book page directly instead of first checking that the ID exists,
+    /// saving a request. An invalid ID then fails with a `ScraperError` instead of returning `Ok(None)`.
+    pub fn skip_verification(mut self, skip: bool) -> Self {
+        self.state.1 = skip;
+        self
+    }
+
     pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
-        let id = &self.state.0;
-        if !verify_id_exists(id).await {
+        let id = match &self.state.0 {
+            Ok(id) => id.clone(),
+            Err(error) => {
+                return Err(error.try_clone().unwrap_or_else(|| {
+                    ScraperError::ParseError("Goodreads ID conversion failed".to_string())
+                }));
+            }
+        };
+        if !self.state.1 && !verify_id_exists(&self.client, &id).await? {
             return Ok(None);
         }
-        Ok(Some(fetch_metadata(id).await?))
+        Ok(Some(
+            fetch_metadata(&self.client, id.as_str(), &self.options).await?,
+        ))
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
     }
 }
 
 impl MetadataRequestBuilder<IsbnState> {
+    /// When `prefer` is true, fetches the metadata of the work's default edition instead of the edition the
+    /// ISBN resolves to, which often has richer data. Fails with `ScraperError::ScrapeError` when the work's
+    /// default edition cannot be resolved. The `isbn` field still holds the requested ISBN (the one
+    /// that resolved, with `with_isbns`), normalized like `Isbn`, so a requested ISBN that is not valid fails
+    /// with `ScraperError::ParseError`. This costs one additional request, or two when Goodreads lists several
+    /// books for the ISBN instead of redirecting to one.
+    pub fn prefer_default_edition(mut self, prefer: bool) -> Self {
+        self.state.1 = prefer;
+        self
+    }
+
+    pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
+        let Some((isbn, id)) = self.resolve_isbn().await? else {
+            return Ok(None);
+        };
+
+        let mut metadata = fetch_metadata(&self.client, &id, &self.options).await?;
+        if self.state.1 && self.options.includes(MetadataField::Isbn) {
+            metadata.isbn = Some(Isbn::parse(isbn)?.as_str().to_string());
+        }
+
+        Ok(Some(metadata))
+    }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page. With
+    /// `prefer_default_edition`, this is the default edition's ID, which still costs the additional requests.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        Ok(self.resolve_isbn().await?.map(|(_, id)| id))
+    }
+
+    // Returns the first ISBN that resolves, with the Goodreads ID to scrape for it.
+    async fn resolve_isbn(&self) -> Result<Option<(&str, String)>, ScraperError> {
+        if self.state.0.is_empty() {
+            return Err(ScraperError::ParseError("No ISBN given".to_string()));
+        }
+
+        let mut resolved = None;
+        for isbn in &self.state.0 {
+            if let Some(found) = fetch_isbn_match(&self.client, isbn).await? {
+                resolved = Some((isbn.as_str(), found));
+                break;
+            }
+        }
+        let Some((isbn, (id, page_data))) = resolved else {
+            return Ok(None);
+        };
+
+        if !self.state.1 {
+            return Ok(Some((isbn, id)));
+        }
+
+        // A redirected search already served the book page, so its work ID is read from there.
+        let work_id = if let Some(page_data) = page_data {
+            extract_page_work_id(&page_data, &id)?
+        } else {
+            let work_options = ExtractOptions {
+                fields: Some(vec![MetadataField::WorkId]),
+                ..ExtractOptions::default()
+            };
+            fetch_metadata(&self.client, &id, &work_options)
+                .await?
+                .work_id
+        };
+        let Some(work_id) = work_id else {
+            warn!("Book {id} has no work ID, keeping the edition of ISBN {isbn}");
+            return Ok(Some((isbn, id)));
+        };
+
+        match fetch_id_from_work_id(&self.client, &work_id).await? {
+            Some(default_id) => Ok(Some((isbn, default_id))),
+            None => Err(ScraperError::ScrapeError(format!(
+                "Failed to resolve the default edition of work {work_id}"
+            ))),
+        }
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
+}
+
+impl MetadataRequestBuilder<AsinState> {
+    pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        fetch_id_from_asin(&self.client, &self.state.0).await
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
+}
+
+impl MetadataRequestBuilder<WorkIdState> {
     pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
-        let isbn = &self.state.0;
-        let goodreads_id = fetch_id_from_isbn(isbn).await?;
-        match goodreads_id {
-            Some(id) => Ok(Some(fetch_metadata(&id).await?)),
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
             None => Ok(None),
         }
     }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        fetch_id_from_work_id(&self.client, &self.state.0).await
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
 }
 
 impl MetadataRequestBuilder<TitleWithAuthorState> {
     pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
-        let title = &self.state.0;
-        let author = &self.state.1;
-        let goodreads_id = fetch_id_from_title_and_author(title, author).await?;
-        match goodreads_id {
-            Some(id) => Ok(Some(fetch_metadata(&id).await?)),
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        fetch_id_from_title_and_author(&self.client, &self.state.0, &self.state.1, &self.state.2)
+            .await
+    }
+
+    /// Like `execute`, but also returns how confident the match is, from 0.0 to 1.0 (an exact title and author).
+    pub async fn execute_with_confidence(
+        &self,
+    ) -> Result<Option<(BookMetadata, f32)>, ScraperError> {
+        let SearchOutcome::Found(result) = self.search().await? else {
+            return Ok(None);
+        };
+
+        let confidence = match_confidence(&result, &self.state.0, Some(&self.state.1));
+        let metadata = fetch_metadata(&self.client, &result.goodreads_id, &self.options).await?;
+        Ok(Some((metadata, confidence)))
+    }
+
+    /// Fetches the matched edition, followed by the work's other editions when `all_editions` is set.
+    /// Returns an empty list when no book matches.
+    pub async fn execute_all(&self) -> Result<Vec<BookMetadata>, ScraperError> {
+        match self.search().await?.goodreads_id() {
+            Some(id) => fetch_all_editions(&self.client, id, &self.options, &self.state.2).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Runs only the title and author search, reporting whether it found a match, no results, or only non-matching candidates.
+    pub async fn search(&self) -> Result<SearchOutcome, ScraperError> {
+        search_title_and_author(&self.client, &self.state.0, &self.state.1, &self.state.2).await
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
+}
+
+impl MetadataRequestBuilder<TitleWithAuthorIdState> {
+    pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        fetch_id_from_author_books(&self.client, &self.state.1, &self.state.0, &self.state.2).await
+    }
+
+    /// Fetches the matched edition, followed by the work's other editions when `all_editions` is set.
+    /// Returns an empty list when no book matches.
+    pub async fn execute_all(&self) -> Result<Vec<BookMetadata>, ScraperError> {
+        match self.search().await?.goodreads_id() {
+            Some(id) => fetch_all_editions(&self.client, id, &self.options, &self.state.2).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Runs only the search through the author's book list, reporting whether it found a match, no books, or only non-matching candidates.
+    pub async fn search(&self) -> Result<SearchOutcome, ScraperError> {
+        search_author_books(&self.client, &self.state.1, &self.state.0, &self.state.2).await
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
+}
+
+impl MetadataRequestBuilder<AuthorState> {
+    /// Fetches the metadata of every book on the first search results page that lists the author.
+    /// This makes one request per book, use `search` to only list the matching books.
+    pub async fn execute(&self) -> Result<Vec<BookMetadata>, ScraperError> {
+        let mut books = Vec::new();
+        for result in self.search().await? {
+            self.client.pace().await;
+            books.push(fetch_metadata(&self.client, &result.goodreads_id, &self.options).await?);
+        }
+
+        Ok(books)
+    }
+
+    /// Lists the books on the first search results page that list the author, without scraping them.
+    pub async fn search(&self) -> Result<Vec<SearchResult>, ScraperError> {
+        search_author(&self.client, &self.state.0).await
+    }
+}
+
+impl MetadataRequestBuilder<DynamicState> {
+    /// Starts a request whose criteria are decided at runtime. Any combination of ID, ISBN, title and author
+    /// can be set, and `execute` uses the most specific one: the ID, then the ISBN, then the title (with the
+    /// author when set). Unlike the other builders, every setter keeps the same type, so they can be called
+    /// conditionally.
+    pub fn dynamic() -> Self {
+        MetadataRequestBuilder::default().into_dynamic()
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.state.id = Some(id.to_string());
+        self
+    }
+
+    pub fn with_isbn(mut self, isbn: impl AsRef<str>) -> Self {
+        self.state.isbn = Some(isbn.as_ref().to_string());
+        self
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.state.title = Some(title.to_string());
+        self
+    }
+
+    /// Narrows a title search. Ignored when an ID or ISBN is set, or when there is no title.
+    pub fn with_author(mut self, author: &str) -> Self {
+        self.state.author = Some(author.to_string());
+        self
+    }
+
+    /// Fails with `ScraperError::ParseError` when neither an ID, an ISBN nor a title is set, or the ID is malformed.
+    pub async fn execute(&self) -> Result<Option<BookMetadata>, ScraperError> {
+        match self.resolve_id().await? {
+            Some(id) => Ok(Some(
+                fetch_metadata(&self.client, &id, &self.options).await?,
+            )),
             None => Ok(None),
         }
     }
+
+    /// Resolves the Goodreads ID `execute` would scrape, without fetching the book page.
+    pub async fn resolve_id(&self) -> Result<Option<String>, ScraperError> {
+        let DynamicState {
+            id,
+            isbn,
+            title,
+            author,
+            search_options,
+        } = &self.state;
+
+        match (id, isbn, title, author) {
+            (Some(id), _, _, _) => {
+                let id = GoodreadsId::parse(id)?;
+                let exists = verify_id_exists(&self.client, &id).await?;
+                Ok(Some(id.to_string()).filter(|_| exists))
+            }
+            (None, Some(isbn), _, _) => fetch_id_from_isbn(&self.client, isbn).await,
+            (None, None, Some(title), Some(author)) => {
+                fetch_id_from_title_and_author(&self.client, title, author, search_options).await
+            }
+            (None, None, Some(title), None) => {
+                fetch_id_from_title(&self.client, title, search_options).await
+            }
+            (None, None, None, _) => Err(ScraperError::ParseError(
+                "A request needs a Goodreads ID, an ISBN or a title".to_string(),
+            )),
+        }
+    }
+
+    /// Like `execute`, but returns `ScraperError::NotFound` instead of `Ok(None)` when no book is found.
+    pub async fn execute_required(&self) -> Result<BookMetadata, ScraperError> {
+        self.execute().await?.ok_or(ScraperError::NotFound)
+    }
+}
+
+// The matched edition comes first, followed by the work's other editions in the order Goodreads lists them.
+async fn fetch_all_editions(
+    client: &GoodreadsClient,
+    goodreads_id: &str,
+    options: &ExtractOptions,
+    search_options: &SearchOptions,
+) -> Result<Vec<BookMetadata>, ScraperError> {
+    let (matched, work_id) = fetch_metadata_and_work_id(client, goodreads_id, options).await?;
+    if !search_options.all_editions {
+        return Ok(vec![matched]);
+    }
+
+    let Some(work_id) = work_id else {
+        return Ok(vec![matched]);
+    };
+
+    let limit = search_options.max_editions.unwrap_or(DEFAULT_MAX_EDITIONS);
+    let editions = fetch_editions(client, &work_id).await?;
+    let mut books = vec![matched];
+
+    for edition in editions
+        .iter()
+        .filter(|edition| edition.goodreads_id != goodreads_id)
+        .take(limit.saturating_sub(1))
+    {
+        client.pace().await;
+        books.push(fetch_metadata(client, &edition.goodreads_id, options).await?);
+    }
+
+    Ok(books)
 }
@@ -0,0 +1,193 @@
+use chrono::{DateTime, Datelike};
+use grscraper::{BookContributor, BookMetadata, BookSeries};
+#[cfg(feature = "net")]
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// Fixtures hold the `__NEXT_DATA__` JSON of a book page, trimmed to the nodes the scraper reads.
+fn parse_fixture(json: &str, goodreads_id: &str) -> BookMetadata {
+    let html = format!(
+        r#"<html><body><script id="__NEXT_DATA__" type="application/json">{json}</script></body></html>"#
+    );
+    BookMetadata::from_html(&html, goodreads_id).unwrap()
+}
+
+#[test]
+fn parse_book_fixture_test() {
+    let metadata = parse_fixture(include_str!("fixtures/4556058.json"), "4556058");
+
+    let mut expected_series = BookSeries::new("Percy Jackson and the Olympians".to_string(), 5.0);
+    expected_series.id = Some("41523".to_string());
+
+    let mut expected_metadata = BookMetadata::new(
+        "The Last Olympian".to_string(),
+        None,
+        Some("All year the half-bloods have been preparing for battle against the Titans, knowing the odds of victory are grim. \
+        Kronos's army is stronger than ever.".to_string()),
+        Some("Disney-Hyperion Books".to_string()),
+        Some(DateTime::parse_from_rfc3339("2009-05-05T07:00:00Z").unwrap().to_utc()),
+        Some("1423101472".to_string()),
+        vec![BookContributor::new("Rick Riordan".to_string(), "Author".to_string())],
+        vec!["Fantasy".to_string(), "Young Adult".to_string(), "Mythology".to_string()],
+        Some(expected_series),
+        Some(381),
+        Some("English".to_string()),
+        Some("https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1723393514i/4556058.jpg".to_string()),
+    );
+    expected_metadata.work_id = Some("4551489".to_string());
+    expected_metadata.edition_count = Some(164);
+    expected_metadata.format = Some("Hardcover".to_string());
+    expected_metadata.similar_books = vec!["28187".to_string()];
+    expected_metadata.publication_year = Some(2009);
+    expected_metadata.goodreads_id = Some("4556058".to_string());
+    expected_metadata.publication_timestamp_ms = Some(1_241_506_800_000);
+    expected_metadata.language_code = Some("en".to_string());
+
+    assert_eq!(metadata, expected_metadata);
+    assert_eq!(
+        metadata.series.and_then(|s| s.id),
+        Some("41523".to_string())
+    );
+}
+
+#[test]
+fn parse_anthology_fixture_test() {
+    let metadata = parse_fixture(include_str!("fixtures/anthology.json"), "1000");
+
+    assert_eq!(metadata.title, "The Best Horror of the Year");
+    assert_eq!(metadata.subtitle.as_deref(), Some("Volume One"));
+    assert_eq!(
+        metadata.contributors,
+        vec![
+            BookContributor::new("Ellen Datlow".to_string(), "Editor".to_string()),
+            BookContributor::new("Neil Gaiman".to_string(), "Contributor".to_string()),
+        ]
+    );
+    assert_eq!(metadata.publisher.as_deref(), Some("Night Shade Books"));
+    assert_eq!(
+        metadata.publication_date.map(|date| date.year()),
+        Some(-699)
+    );
+    assert_eq!(metadata.publication_year, Some(-699));
+    assert_eq!(metadata.publication_timestamp_ms, None);
+    assert_eq!(metadata.isbn.as_deref(), Some("9781597801348"));
+    assert_eq!(metadata.page_count, None);
+    assert_eq!(metadata.language, None);
+    assert_eq!(metadata.description, None);
+    assert_eq!(metadata.series, None);
+    assert!(metadata.genres.is_empty());
+    assert_eq!(metadata.work_id.as_deref(), Some("2000"));
+    assert_eq!(metadata.edition_count, Some(2));
+    assert_eq!(metadata.goodreads_id.as_deref(), Some("1000"));
+}
+
+// Serves the fixture as the page of book 4556058, also reached by redirect from its ISBN search and from its
+// work's default edition, and 404s for everything else.
+#[cfg(feature = "net")]
+#[derive(Default)]
+struct FixtureFetcher {
+    // How many pages cut off halfway to serve before the whole page.
+    truncated: AtomicU32,
+}
+
+#[cfg(feature = "net")]
+impl grscraper::HttpFetcher for FixtureFetcher {
+    fn get<'a>(
+        &'a self,
+        url: &'a str,
+    ) -> futures::future::BoxFuture<'a, Result<grscraper::HttpResponse, grscraper::ScraperError>>
+    {
+        Box::pin(async move {
+            let json = include_str!("fixtures/4556058.json");
+            match url {
+                "https://www.goodreads.com/book/show/4556058" => (),
+                "https://www.goodreads.com/search?q=9781423101475" => {
+                    let json = json.replacen(
+                        r#""pageProps": {"#,
+                        r#""pageProps": {"params": {"book_id": "4556058-the-last-olympian"},"#,
+                        1,
+                    );
+                    let html = format!(
+                        r#"<html><body><script id="__NEXT_DATA__" type="application/json">{json}</script></body></html>"#
+                    );
+                    return Ok(grscraper::HttpResponse::new(
+                        "https://www.goodreads.com/book/show/4556058-the-last-olympian",
+                        html,
+                    ));
+                }
+                "https://www.goodreads.com/work/best_book/4551489" => {
+                    return Ok(grscraper::HttpResponse::new(
+                        "https://www.goodreads.com/book/show/4556058-the-last-olympian",
+                        "",
+                    ));
+                }
+                _ => return Err(grscraper::ScraperError::HttpStatus(404)),
+            }
+            let html = format!(
+                r#"<html><body><script id="__NEXT_DATA__" type="application/json">{json}</script></body></html>"#
+            );
+            if self
+                .truncated
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Ok(grscraper::HttpResponse::new(
+                    url,
+                    &html.as_bytes()[..html.len() / 2],
+                ));
+            }
+            Ok(grscraper::HttpResponse::new(url, html))
+        })
+    }
+}
+
+#[cfg(feature = "net")]
+#[tokio::test]
+async fn custom_fetcher_test() {
+    let client = grscraper::GoodreadsClient::from_fetcher(FixtureFetcher::default());
+
+    let metadata = client.metadata_by_id("4556058").await.unwrap().unwrap();
+    assert_eq!(metadata.title, "The Last Olympian");
+    assert_eq!(metadata.goodreads_id.as_deref(), Some("4556058"));
+    // The ID is verified before the page is scraped.
+    assert_eq!(client.request_count(), 2);
+
+    assert_eq!(client.metadata_by_id("1").await.unwrap(), None);
+}
+
+#[cfg(feature = "net")]
+#[tokio::test]
+async fn truncated_page_retry_test() {
+    let fetcher = FixtureFetcher {
+        truncated: AtomicU32::new(2),
+    };
+    let client = grscraper::GoodreadsClient::from_fetcher(fetcher);
+    assert!(client.fetch_page_data("4556058").await.is_ok());
+
+    let fetcher = FixtureFetcher {
+        truncated: AtomicU32::new(3),
+    };
+    let client = grscraper::GoodreadsClient::from_fetcher(fetcher);
+    assert!(matches!(
+        client.fetch_page_data("4556058").await,
+        Err(grscraper::ScraperError::SerializeError(_))
+    ));
+}
+
+#[cfg(feature = "net")]
+#[tokio::test]
+async fn prefer_default_edition_fixture_test() {
+    let client = grscraper::GoodreadsClient::from_fetcher(FixtureFetcher::default());
+
+    let metadata = client
+        .request()
+        .with_isbn("978-1-4231-0147-5")
+        .prefer_default_edition(true)
+        .execute()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(metadata.goodreads_id.as_deref(), Some("4556058"));
+    assert_eq!(metadata.isbn.as_deref(), Some("9781423101475"));
+    // The work ID is read from the page the ISBN search redirected to.
+    assert_eq!(client.request_count(), 3);
+}
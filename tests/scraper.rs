@@ -1,4 +1,11 @@
-use grscraper::{BookContributor, BookMetadata, BookSeries, MetadataRequestBuilder};
+#![cfg(feature = "net")]
+
+use futures::StreamExt;
+use grscraper::{
+    BookContributor, BookMetadata, BookSeries, GoodreadsClient, GoodreadsId,
+    MetadataRequestBuilder, ScraperError,
+};
+use std::collections::HashSet;
 
 #[tokio::test]
 async fn fetch_metadata_by_title_test() {
@@ -12,6 +19,68 @@ async fn fetch_metadata_by_title_test() {
     verify_metadata(metadata.as_ref());
 }
 
+#[tokio::test]
+async fn resolve_id_test() {
+    let id = MetadataRequestBuilder::default()
+        .with_title("The Last Magician")
+        .with_author("Lisa Maxwell")
+        .resolve_id()
+        .await
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("30312855"));
+
+    let id = MetadataRequestBuilder::default()
+        .with_isbn("1481432079")
+        .resolve_id()
+        .await
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("30312855"));
+}
+
+#[tokio::test]
+async fn fetch_metadata_dynamic_test() {
+    let metadata = MetadataRequestBuilder::dynamic()
+        .with_title("The Last Magician")
+        .with_author("Lisa Maxwell")
+        .execute()
+        .await
+        .unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let id = MetadataRequestBuilder::dynamic()
+        .with_title("The Last Olympian")
+        .with_isbn("1481432079")
+        .resolve_id()
+        .await
+        .unwrap();
+    assert_eq!(id.as_deref(), Some("30312855"));
+
+    let result = MetadataRequestBuilder::dynamic()
+        .with_author("Lisa Maxwell")
+        .execute()
+        .await;
+    assert!(matches!(result, Err(ScraperError::ParseError(_))));
+}
+
+#[tokio::test]
+async fn fetch_metadata_by_id_conversion_error_test() {
+    struct ExportedId;
+
+    impl TryFrom<ExportedId> for GoodreadsId {
+        type Error = ScraperError;
+
+        fn try_from(_: ExportedId) -> Result<Self, Self::Error> {
+            Err(ScraperError::Unavailable)
+        }
+    }
+
+    let result = MetadataRequestBuilder::default()
+        .with_id(ExportedId)
+        .execute()
+        .await;
+    assert!(matches!(result, Err(ScraperError::Unavailable)));
+}
+
 #[tokio::test]
 async fn fetch_metadata_by_id_test() {
     let id = "30312855";
@@ -24,6 +93,33 @@ async fn fetch_metadata_by_id_test() {
     verify_metadata(metadata.as_ref());
 }
 
+#[tokio::test]
+async fn fetch_metadata_skip_verification_test() {
+    let metadata = MetadataRequestBuilder::default()
+        .with_id("30312855")
+        .skip_verification(true)
+        .execute()
+        .await
+        .unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let result = MetadataRequestBuilder::default()
+        .with_id("999999999999")
+        .skip_verification(true)
+        .execute()
+        .await;
+    assert!(matches!(result, Err(ScraperError::HttpStatus(404))));
+}
+
+#[tokio::test]
+async fn fetch_metadata_by_malformed_id_test() {
+    let result = MetadataRequestBuilder::default()
+        .with_id("bad_id")
+        .execute()
+        .await;
+    assert!(matches!(result, Err(ScraperError::ParseError(_))));
+}
+
 #[tokio::test]
 async fn fetch_metadata_by_isbn_test() {
     let isbn = "1481432079";
@@ -36,6 +132,38 @@ async fn fetch_metadata_by_isbn_test() {
     verify_metadata(metadata.as_ref());
 }
 
+#[tokio::test]
+async fn fetch_metadata_by_isbns_test() {
+    let metadata = MetadataRequestBuilder::default()
+        .with_isbns(&["1234001592323", "1481432079"])
+        .execute()
+        .await
+        .unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let result = MetadataRequestBuilder::default()
+        .with_isbns(&[])
+        .execute()
+        .await;
+    assert!(matches!(result, Err(ScraperError::ParseError(_))));
+}
+
+#[tokio::test]
+async fn fetch_metadata_shorthand_test() {
+    let metadata = grscraper::by_isbn("1481432079").execute().await.unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let metadata = grscraper::by_id("30312855").execute().await.unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let metadata = grscraper::by_title("The Last Magician")
+        .with_author("Lisa Maxwell")
+        .execute()
+        .await
+        .unwrap();
+    verify_metadata(metadata.as_ref());
+}
+
 #[tokio::test]
 async fn fetch_metadata_by_isbn_bad_time_test() {
     let isbn = "9788467271300";
@@ -45,7 +173,7 @@ async fn fetch_metadata_by_isbn_bad_time_test() {
         .await
         .unwrap();
 
-    let expected_metadata = BookMetadata::new(
+    let mut expected_metadata = BookMetadata::new(
         "El Fuego Invisible".to_string(),
         None,
         None,
@@ -59,6 +187,13 @@ async fn fetch_metadata_by_isbn_bad_time_test() {
         Some("Spanish; Castilian".to_string()),
         None,
     );
+    copy_work_details(metadata.as_ref(), &mut expected_metadata);
+    // Without an edition date, the year falls back to the work's original publication year, which is not pinned.
+    expected_metadata.publication_year = metadata.as_ref().and_then(|m| m.publication_year);
+    // The edition has no description, so it falls back to the work's, if any.
+    expected_metadata.description = metadata.as_ref().and_then(|m| m.work_description.clone());
+    expected_metadata.goodreads_id = metadata.as_ref().and_then(|m| m.goodreads_id.clone());
+    expected_metadata.language_code = Some("es".to_string());
 
     assert_eq!(metadata, Some(expected_metadata));
 }
@@ -77,6 +212,157 @@ async fn fetch_metadata_by_title_with_author_test() {
     verify_metadata(metadata.as_ref());
 }
 
+#[tokio::test]
+async fn fetch_metadata_with_client_test() {
+    let client = GoodreadsClient::builder().build().unwrap();
+    let metadata = client.metadata_by_id("30312855").await.unwrap();
+    verify_metadata(metadata.as_ref());
+
+    let metadata = client.metadata_by_isbn("1481432079").await.unwrap();
+    verify_metadata(metadata.as_ref());
+}
+
+#[tokio::test]
+async fn fetch_metadata_required_not_found_test() {
+    let title = "thistitledoesnotexist";
+    let result = MetadataRequestBuilder::default()
+        .with_title(title)
+        .execute_required()
+        .await;
+
+    assert!(matches!(result, Err(ScraperError::NotFound)));
+}
+
+#[tokio::test]
+async fn fetch_cover_test() {
+    let client = GoodreadsClient::default();
+    let mut metadata = client.metadata_by_id("4556058").await.unwrap().unwrap();
+    let cover = client.fetch_cover(&metadata).await.unwrap();
+    assert!(cover.starts_with(&[0xFF, 0xD8, 0xFF]));
+
+    metadata.image_url = None;
+    let result = client.fetch_cover(&metadata).await;
+    assert!(matches!(result, Err(ScraperError::ScrapeError(_))));
+}
+
+#[tokio::test]
+async fn refresh_test() {
+    let client = GoodreadsClient::default();
+    let mut metadata = client.metadata_by_id("30312855").await.unwrap().unwrap();
+    metadata.page_count = None;
+    let refreshed = metadata.refresh().await.unwrap();
+    assert_eq!(refreshed.goodreads_id.as_deref(), Some("30312855"));
+    assert!(refreshed.page_count.is_some());
+
+    metadata.goodreads_id = None;
+    let result = client.refresh(&metadata).await;
+    assert!(matches!(result, Err(ScraperError::ScrapeError(_))));
+}
+
+#[tokio::test]
+async fn fetch_metadata_with_shelves_test() {
+    let metadata = MetadataRequestBuilder::default()
+        .with_id("4556058")
+        .with_shelves()
+        .execute_required()
+        .await
+        .unwrap();
+
+    assert!(!metadata.shelves.is_empty());
+    assert!(metadata.shelves.iter().all(|(_, count)| *count > 0));
+}
+
+#[tokio::test]
+async fn fetch_metadata_prefer_default_edition_test() {
+    let isbn = "9780141381473";
+    let metadata = MetadataRequestBuilder::default()
+        .with_isbn(isbn)
+        .prefer_default_edition(true)
+        .execute_required()
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.title, "The Lightning Thief");
+    assert_eq!(metadata.isbn.as_deref(), Some(isbn));
+
+    let isbn_edition = MetadataRequestBuilder::default()
+        .with_isbn(isbn)
+        .resolve_id()
+        .await
+        .unwrap();
+    let default_edition = MetadataRequestBuilder::default()
+        .with_work_id(metadata.work_id.as_deref().unwrap())
+        .resolve_id()
+        .await
+        .unwrap();
+    assert!(default_edition.is_some());
+    assert_eq!(metadata.goodreads_id, default_edition);
+    assert_ne!(metadata.goodreads_id, isbn_edition);
+}
+
+#[tokio::test]
+async fn fetch_metadata_preferred_language_test() {
+    let metadata = MetadataRequestBuilder::default()
+        .with_id("4556058")
+        .with_preferred_language("French")
+        .execute_required()
+        .await
+        .unwrap();
+    assert_eq!(metadata.language.as_deref(), Some("French"));
+    assert_eq!(metadata.language_code.as_deref(), Some("fr"));
+
+    let metadata = MetadataRequestBuilder::default()
+        .with_id("4556058")
+        .with_preferred_language("Klingon")
+        .execute_required()
+        .await
+        .unwrap();
+    assert_eq!(metadata.title, "The Last Olympian");
+    assert_eq!(metadata.language.as_deref(), Some("English"));
+}
+
+#[tokio::test]
+async fn fetch_all_editions_test() {
+    let editions = MetadataRequestBuilder::default()
+        .with_title("The Last Olympian")
+        .with_author("Rick Riordan")
+        .all_editions(true)
+        .with_max_editions(3)
+        .execute_all()
+        .await
+        .unwrap();
+
+    assert_eq!(editions.len(), 3);
+    assert!(editions.iter().all(|e| e.work_id == editions[0].work_id));
+}
+
+#[tokio::test]
+async fn metadata_stream_test() {
+    let isbns = ["1481432079", "1234001592323"].map(str::to_string);
+    let mut results = GoodreadsClient::default()
+        .metadata_stream(isbns, 2)
+        .collect::<Vec<_>>()
+        .await;
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(&results[0], (isbn, Ok(None)) if isbn == "1234001592323"));
+    assert!(matches!(&results[1], (isbn, Ok(Some(_))) if isbn == "1481432079"));
+}
+
+#[tokio::test]
+async fn resume_metadata_stream_test() {
+    let isbns = ["1481432079", "1234001592323"].map(str::to_string);
+    let completed = HashSet::from(["1234001592323".to_string()]);
+    let results = GoodreadsClient::default()
+        .resume_metadata_stream(isbns, completed, 2)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(results.len(), 1);
+    assert!(matches!(&results[0], (isbn, Ok(Some(_))) if isbn == "1481432079"));
+}
+
 fn verify_metadata(metadata: Option<&BookMetadata>) {
     let expected_series = BookSeries::new("The Last Magician".to_string(), 1.0);
     let expected_contributors = vec![BookContributor::new(
@@ -95,7 +381,7 @@ fn verify_metadata(metadata: Option<&BookMetadata>) {
         "Urban Fantasy".to_string(),
         "Audiobook".to_string(),
     ];
-    let expected_metadata = BookMetadata::new(
+    let mut expected_metadata = BookMetadata::new(
         "The Last Magician".to_string(),
         None,
         Some("<i>Stop the Magician. Steal the book. Save the future.</i><br /><br />In modern-day New York, magic is all but extinct. \
@@ -118,6 +404,37 @@ fn verify_metadata(metadata: Option<&BookMetadata>) {
         Some("English".to_string()),
         Some("https://m.media-amazon.com/images/S/compressed.photo.goodreads.com/books/1468598919i/30312855.jpg".to_string())
     );
+    expected_metadata.format = Some("Hardcover".to_string());
+    expected_metadata.publication_year = Some(2017);
+    expected_metadata.goodreads_id = Some("30312855".to_string());
+    expected_metadata.publication_timestamp_ms = Some(1_500_361_200_000);
+    expected_metadata.language_code = Some("en".to_string());
+    copy_work_details(metadata, &mut expected_metadata);
+
+    let metadata = metadata.expect("Book not found");
+    assert!(
+        metadata.content_eq(&expected_metadata),
+        "{metadata:#?} != {expected_metadata:#?}"
+    );
+}
+
+// Work identifiers and edition counts are not pinned, only checked for presence. Physical details, recommendations,
+// the work description, popularity and rating counts are not pinned either.
+fn copy_work_details(metadata: Option<&BookMetadata>, expected: &mut BookMetadata) {
+    let Some(metadata) = metadata else {
+        return;
+    };
 
-    assert_eq!(metadata, Some(expected_metadata).as_ref());
+    assert!(metadata.work_id.is_some());
+    assert!(metadata.edition_count.is_some_and(|count| count > 0));
+    expected.work_id.clone_from(&metadata.work_id);
+    expected.edition_count = metadata.edition_count;
+    expected.dimensions.clone_from(&metadata.dimensions);
+    expected.weight.clone_from(&metadata.weight);
+    expected.similar_books.clone_from(&metadata.similar_books);
+    expected
+        .work_description
+        .clone_from(&metadata.work_description);
+    expected.edition_popularity = metadata.edition_popularity;
+    expected.ratings_histogram = metadata.ratings_histogram;
 }